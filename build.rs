@@ -0,0 +1,104 @@
+//! Exposes build settings to the crate and generates the include files used by the
+//! `embed-inputs` feature.
+//!
+//! The `AOC_BUILD_*` environment variables describe the build for timing fingerprints.
+//!
+//! For every day, `$OUT_DIR/input-<day>.rs` either embeds `data/inputs/<day>.txt` via
+//! `include_str!` or, if that file is missing or empty, raises a descriptive `compile_error!`.
+//! `$OUT_DIR/embedded_examples.rs` embeds every non-empty file in `data/examples`.
+use std::{env, fs, path::Path, process::Command};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    emit_build_info();
+
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_none() {
+        return;
+    }
+
+    embed_inputs();
+}
+
+fn emit_build_info() {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map_or_else(|| "unknown".into(), |v| v.trim().to_string());
+
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|f| f.to_lowercase().replace('_', "-"))
+        })
+        .collect();
+    features.sort();
+
+    let var = |key: &str| env::var(key).unwrap_or_else(|_| "unknown".into());
+
+    println!("cargo:rustc-env=AOC_BUILD_RUSTC={rustc_version}");
+    println!("cargo:rustc-env=AOC_BUILD_TARGET={}", var("TARGET"));
+    println!("cargo:rustc-env=AOC_BUILD_PROFILE={}", var("PROFILE"));
+    println!("cargo:rustc-env=AOC_BUILD_OPT_LEVEL={}", var("OPT_LEVEL"));
+    println!("cargo:rustc-env=AOC_BUILD_FEATURES={}", features.join(","));
+}
+
+fn embed_inputs() {
+    let root = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let inputs_dir = Path::new(&root).join("data").join("inputs");
+    let examples_dir = Path::new(&root).join("data").join("examples");
+
+    println!("cargo:rerun-if-changed={}", inputs_dir.display());
+    println!("cargo:rerun-if-changed={}", examples_dir.display());
+
+    for day in 1..=25 {
+        let path = inputs_dir.join(format!("{day:02}.txt"));
+        let contents = if is_non_empty(&path) {
+            format!("include_str!({:?})", path.display().to_string())
+        } else {
+            let message = format!(
+                "`embed-inputs` requires \"data/inputs/{day:02}.txt\" to exist and be non-empty. \
+                Run `cargo download {day}` before building with `--features embed-inputs`."
+            );
+            format!("compile_error!({message:?})")
+        };
+        fs::write(
+            Path::new(&out_dir).join(format!("input-{day}.rs")),
+            contents,
+        )
+        .unwrap();
+    }
+
+    let mut examples: Vec<String> = fs::read_dir(&examples_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .filter(|path| is_non_empty(path))
+                .map(|path| {
+                    let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+                    format!(
+                        "({file_name:?}, include_str!({:?}))",
+                        path.display().to_string()
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    examples.sort();
+
+    fs::write(
+        Path::new(&out_dir).join("embedded_examples.rs"),
+        format!("&[{}]", examples.join(",\n")),
+    )
+    .unwrap();
+}
+
+fn is_non_empty(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|s| !s.trim().is_empty())
+}
@@ -4,17 +4,11 @@ advent_of_code::solution!(1);
 use counter::Counter;
 use rustc_hash::FxBuildHasher;
 
-use winnow::{
-    ascii::{dec_uint, newline, space1},
-    combinator::{separated, separated_pair},
-    Parser,
-};
-
-fn parse_line(input: &mut &[u8]) -> winnow::PResult<(u32, u32)> {
-    separated_pair(dec_uint::<_, u32, _>, space1, dec_uint).parse_next(input)
-}
+use advent_of_code::parse::{column_pair, lines, parse, ParseError};
+use winnow::Parser;
+
 fn parse_to_vec(input: &mut &[u8]) -> winnow::PResult<(Vec<u32>, Vec<u32>)> {
-    separated(1.., parse_line, newline)
+    lines(column_pair::<_, u32>)
         .map(|v: Vec<_>| v.into_iter().unzip())
         .parse_next(input)
 }
@@ -22,30 +16,28 @@ fn parse_to_vec(input: &mut &[u8]) -> winnow::PResult<(Vec<u32>, Vec<u32>)> {
 type UCounter = Counter<u32, u32, FxBuildHasher>;
 
 fn parse_to_counter(input: &mut &[u8]) -> winnow::PResult<(Vec<u32>, UCounter)> {
-    separated(1.., parse_line, newline)
+    lines(column_pair::<_, u32>)
         .map(|v: Vec<_>| v.into_iter().unzip())
         .parse_next(input)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut input = input.as_bytes();
-    let (mut col1, mut col2) = parse_to_vec(&mut input).unwrap();
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let (mut col1, mut col2) = parse(input.as_bytes(), parse_to_vec)?;
 
-    col1.sort();
-    col2.sort();
+    col1.sort_unstable();
+    col2.sort_unstable();
 
     let tot: u32 = col1.into_iter().zip(col2).map(|l| l.0.abs_diff(l.1)).sum();
 
-    Some(tot)
+    Ok(tot)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut input = input.as_bytes();
-    let (col1, col2) = parse_to_counter(&mut input).unwrap();
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let (col1, col2) = parse(input.as_bytes(), parse_to_counter)?;
 
     let a: u32 = col1.iter().map(|f| f * col2[f]).sum();
 
-    Some(a)
+    Ok(a)
 }
 
 #[cfg(test)]
@@ -55,12 +47,22 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
+        assert_eq!(result, Ok(11));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
+        assert_eq!(result, Ok(31));
+    }
+
+    #[test]
+    fn test_allocations() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        // the parsed pairs grow by doubling, after which unzipping allocates each column once.
+        // sorting in place allocates nothing.
+        let budget = 1 + u64::from(input.lines().count().ilog2()) + 2;
+        let result = advent_of_code::assert_max_allocs!(budget, || part_one(&input));
+        assert_eq!(result, Ok(11));
     }
 }
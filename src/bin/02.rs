@@ -1,39 +1,45 @@
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, parse: parse_reports, part_one: [part_one], part_two: [part_two, part_two_naive]);
 
-use winnow::{
-    ascii::{dec_uint, newline, space1},
-    combinator::{repeat, separated, terminated},
-    Parser,
-};
+use advent_of_code::parse::{lines, parse, uints, ParseError};
 
 type Size = u8;
 
-fn parse_line(input: &mut &[u8]) -> winnow::PResult<Vec<Size>> {
-    separated(1.., dec_uint::<_, Size, _>, space1).parse_next(input)
+fn parse_reports(input: &str) -> Result<Vec<Vec<Size>>, ParseError> {
+    parse(input.as_bytes(), lines(uints))
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let mut input = input.as_bytes();
-    let levels: Vec<Vec<Size>> = repeat(0.., terminated(parse_line, newline))
-        .parse_next(&mut input)
-        .unwrap();
-    let levels = levels.into_iter().filter(check_smoothness).count();
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let levels = parse_reports(input)?
+        .into_iter()
+        .filter(check_smoothness)
+        .count();
 
-    Some(levels)
+    Ok(levels)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let mut input = input.as_bytes();
-
-    let levels: Vec<Vec<Size>> = repeat(0.., terminated(parse_line, newline))
-        .parse_next(&mut input)
-        .unwrap();
-    let count = levels
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let count = parse_reports(input)?
         .into_iter()
         .filter(check_smoothness_with_removal)
         .count();
 
-    Some(count)
+    Ok(count)
+}
+
+/// Brute-force reference for `part_two`, trying every single removal.
+pub fn part_two_naive(input: &str) -> Result<usize, ParseError> {
+    let count = parse_reports(input)?
+        .into_iter()
+        .filter(|values| {
+            (0..values.len()).any(|idx| {
+                let mut removed = values.clone();
+                removed.remove(idx);
+                check_smoothness(&removed)
+            })
+        })
+        .count();
+
+    Ok(count)
 }
 
 #[allow(clippy::ptr_arg)]
@@ -122,16 +128,56 @@ fn check_after_removal(values: &[Size], remove_idx: usize, ascending: bool) -> b
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::prop::{self, Rng};
 
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
+        assert_eq!(result, Ok(2));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4));
+        assert_eq!(result, Ok(4));
+    }
+
+    #[test]
+    fn test_part_two_naive() {
+        let result = part_two_naive(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Ok(4));
+    }
+
+    /// Reports that drift up or down in steps of up to 4, so some are safe and some are not. Eight
+    /// steps from 40 or more keep every level positive, so that every report parses.
+    fn generate_reports(rng: &mut Rng) -> String {
+        rng.lines(1..=rng.size(), |rng| {
+            let mut level: i8 = rng.range(40..=60);
+            let levels = rng.vec(5..=8, |rng| {
+                level += rng.range(-4..=4);
+                level.to_string()
+            });
+            levels.join(" ")
+        })
+    }
+
+    #[test]
+    fn test_part_two_agrees_with_naive() {
+        prop::check(
+            generate_reports,
+            prop::agrees_with(part_two, part_two_naive),
+        );
+    }
+
+    #[test]
+    fn test_report_order_is_irrelevant() {
+        prop::check(
+            generate_reports,
+            prop::invariant_under(part_one, prop::reverse_lines),
+        );
+        prop::check(
+            generate_reports,
+            prop::invariant_under(part_two, prop::reverse_lines),
+        );
     }
 }
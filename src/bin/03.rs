@@ -102,4 +102,11 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(48));
     }
+
+    #[test]
+    fn test_allocations() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        advent_of_code::assert_max_allocs!(0, || part_one(&input));
+        advent_of_code::assert_max_allocs!(0, || part_two(&input));
+    }
 }
@@ -1,30 +1,19 @@
+use advent_of_code::parse::{blank_line, lines, parse, rule, uint_list, ParseError};
 use rustc_hash::FxBuildHasher;
-use std::{collections::HashSet, str::FromStr};
+use std::collections::HashSet;
+use winnow::combinator::separated_pair;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse: parse_manuals);
 
 type S = u16;
 
-impl FromStr for Manual {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self {
-            pages: s.split(',').map(|f| f.parse().unwrap()).collect(),
-        })
-    }
-}
 #[derive(Debug)]
 struct Manual {
     pages: Vec<S>,
 }
 
-pub fn part_one(input: &str) -> Option<S> {
-    let (input, rules) = parse_rules(input);
-    let manuals: Vec<Manual> = input
-        .lines()
-        .map(|f| Manual::from_str(f).unwrap())
-        .collect();
+pub fn part_one(input: &str) -> Result<S, ParseError> {
+    let (rules, manuals) = parse_manuals(input)?;
 
     let a: S = manuals
         .into_iter()
@@ -32,15 +21,11 @@ pub fn part_one(input: &str) -> Option<S> {
         .map(|f| f.pages[f.pages.len() / 2])
         .sum();
 
-    Some(a)
+    Ok(a)
 }
 
-pub fn part_two(input: &str) -> Option<S> {
-    let (input, rules) = parse_rules(input);
-    let mut manuals: Vec<Manual> = input
-        .lines()
-        .map(|f| Manual::from_str(f).unwrap())
-        .collect();
+pub fn part_two(input: &str) -> Result<S, ParseError> {
+    let (rules, mut manuals) = parse_manuals(input)?;
 
     let a: S = manuals
         .iter_mut()
@@ -54,37 +39,127 @@ pub fn part_two(input: &str) -> Option<S> {
         })
         .sum();
 
-    Some(a)
+    Ok(a)
 }
 
-fn parse_rules(s: &str) -> (&str, HashSet<(S, S), FxBuildHasher>) {
-    if let Some((l, r)) = s.split_once("\n\n") {
-        let rules: HashSet<(S, S), FxBuildHasher> = l
-            .lines()
-            .map(|f| {
-                let (a, b) = f.split_once('|').unzip();
-                (a.unwrap().parse().unwrap(), b.unwrap().parse().unwrap())
-            })
-            .collect();
-        (r, rules)
-    } else {
-        panic!("Oops")
-    }
+type Rules = HashSet<(S, S), FxBuildHasher>;
+
+fn parse_manuals(input: &str) -> Result<(Rules, Vec<Manual>), ParseError> {
+    let (rules, manuals): (Vec<(S, S)>, Vec<Vec<S>>) = parse(
+        input.as_bytes(),
+        separated_pair(lines(rule), blank_line, lines(uint_list(','))),
+    )?;
+
+    Ok((
+        rules.into_iter().collect(),
+        manuals.into_iter().map(|pages| Manual { pages }).collect(),
+    ))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::prop::{self, Rng};
 
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(143));
+        assert_eq!(result, Ok(143));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(123));
+        assert_eq!(result, Ok(123));
+    }
+
+    /// A rule for every pair of pages of a hidden total order, followed by manuals of an odd
+    /// number of distinct pages in random order.
+    fn generate_manuals(rng: &mut Rng) -> String {
+        let mut pages: Vec<S> = (10..=99).collect();
+        rng.shuffle(&mut pages);
+        pages.truncate(rng.range(2..=20));
+
+        let mut rules: Vec<String> = vec![];
+        for (i, before) in pages.iter().enumerate() {
+            for after in &pages[i + 1..] {
+                rules.push(format!("{before}|{after}"));
+            }
+        }
+        rng.shuffle(&mut rules);
+
+        let manuals = rng.lines(1..=rng.size(), |rng| {
+            let mut manual = pages.clone();
+            rng.shuffle(&mut manual);
+            manual.truncate(rng.range(0..=(pages.len() - 1) / 2) * 2 + 1);
+            if rng.bool() {
+                manual.sort_by_key(|page| pages.iter().position(|p| p == page));
+            }
+            manual
+                .iter()
+                .map(S::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        });
+
+        format!("{}\n\n{manuals}", rules.join("\n"))
+    }
+
+    /// Orders every manual by how many of its other pages each page has to precede.
+    fn reference(input: &str, fix: bool) -> Result<S, ParseError> {
+        let (rules, manuals) = input.split_once("\n\n").unwrap();
+        let rules: Vec<(S, S)> = rules
+            .lines()
+            .map(|l| {
+                let (a, b) = l.split_once('|').unwrap();
+                (a.parse().unwrap(), b.parse().unwrap())
+            })
+            .collect();
+
+        let sum = manuals
+            .lines()
+            .map(|l| {
+                let pages: Vec<S> = l.split(',').map(|p| p.parse().unwrap()).collect();
+                let mut sorted = pages.clone();
+                sorted.sort_by_key(|page| {
+                    std::cmp::Reverse(
+                        rules
+                            .iter()
+                            .filter(|(a, b)| a == page && pages.contains(b))
+                            .count(),
+                    )
+                });
+                match (sorted == pages, fix) {
+                    (true, false) | (false, true) => sorted[sorted.len() / 2],
+                    _ => 0,
+                }
+            })
+            .sum();
+
+        Ok(sum)
+    }
+
+    #[test]
+    fn test_agrees_with_reference() {
+        prop::check(
+            generate_manuals,
+            prop::agrees_with(part_one, |i| reference(i, false)),
+        );
+        prop::check(
+            generate_manuals,
+            prop::agrees_with(part_two, |i| reference(i, true)),
+        );
+    }
+
+    #[test]
+    fn test_rule_and_manual_order_is_irrelevant() {
+        prop::check(
+            generate_manuals,
+            prop::invariant_under(part_one, prop::reverse_lines),
+        );
+        prop::check(
+            generate_manuals,
+            prop::invariant_under(part_two, prop::reverse_lines),
+        );
     }
 }
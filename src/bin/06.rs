@@ -1,134 +1,260 @@
-use std::{collections::HashSet, ops::Range, str::FromStr};
-advent_of_code::solution!(6);
-
-type S = i16;
-
-#[derive(Debug, Clone)]
-struct Guard {
-    domain: (Range<S>, Range<S>),
-    start: (S, S),
-    position: (S, S),
-    direction: (S, S),
-    visited: HashSet<((S, S), (S, S))>,
-    obstacles: HashSet<(S, S)>,
-    done: bool,
-    looped: bool,
+use advent_of_code::grid::Grid;
+use advent_of_code::point::Dir4;
+use advent_of_code::viz::{Cell, Frame, Recorder, Rgb};
+use std::{panic, thread};
+advent_of_code::solution!(6, part_one: [part_one], part_two: [part_two, part_two_parallel]);
+
+/// Marks a guard leaving the lab in the jump tables.
+const EXIT: u32 = u32::MAX;
+
+/// A cell the guard enters for the first time, with the cell and direction she enters it from.
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    cell: usize,
+    from: usize,
+    direction: Dir4,
 }
 
-impl Guard {
-    fn from_start(start: (S, S), domain: (Range<S>, Range<S>), obstacles: HashSet<(S, S)>) -> Self {
-        Guard {
-            start,
-            position: start,
-            direction: (0, -1),
-            visited: HashSet::new(),
-            domain,
-            obstacles,
-            done: false,
-            looped: false,
-        }
-    }
+/// The lab with cells in row-major order.
+#[derive(Debug)]
+struct Lab {
+    width: usize,
+    height: usize,
+    blocked: Vec<bool>,
+    start: usize,
+    /// For every cell and direction, the cell where the guard stops in front of the next
+    /// obstacle, or [`EXIT`] if she leaves the lab instead.
+    jumps: Vec<[u32; 4]>,
+}
+
+impl Lab {
+    fn parse(input: &str) -> Option<Self> {
+        let grid = Grid::parse_with(input, |b| b).ok()?;
+        let (width, height) = (grid.width(), grid.height());
+        let start = grid.find(|&b| b == b'^')?;
+        let blocked: Vec<bool> = grid.rows().flatten().map(|&b| b == b'#').collect();
 
-    fn reset(&mut self) {
-        self.visited = HashSet::new();
-        self.direction = (0, -1);
-        self.position = self.start;
-        self.looped = false;
-        self.done = false;
+        let mut lab = Self {
+            width,
+            height,
+            blocked,
+            start: start.y * width + start.x,
+            jumps: vec![[EXIT; 4]; width * height],
+        };
+        lab.fill_jumps();
+        Some(lab)
     }
 
-    fn step(&mut self) {
-        let pos_n = (
-            self.position.0 + self.direction.0,
-            self.position.1 + self.direction.1,
-        );
-        if !self.domain.0.contains(&pos_n.0) || !self.domain.1.contains(&pos_n.1) {
-            self.done = true;
-        } else if self.obstacles.contains(&pos_n) {
-            self.rotate();
-        } else {
-            self.position = pos_n;
-            if !self.visited.insert((pos_n, self.direction)) {
-                self.looped = true;
+    /// Fills the jump tables row by row and column by column, starting at the edge the guard
+    /// walks towards, so that the jump from the neighbouring cell is always known.
+    fn fill_jumps(&mut self) {
+        let w = self.width;
+        let mut jump = |cell: usize, direction: Dir4, ahead: Option<usize>| {
+            self.jumps[cell][direction as usize] = match ahead {
+                None => EXIT,
+                Some(ahead) if self.blocked[ahead] => cell as u32,
+                Some(ahead) => self.jumps[ahead][direction as usize],
+            };
+        };
+
+        for y in 0..self.height {
+            for x in 0..w {
+                jump(y * w + x, Dir4::Up, (y > 0).then(|| (y - 1) * w + x));
+                jump(y * w + x, Dir4::Left, (x > 0).then(|| y * w + x - 1));
+            }
+        }
+        for y in (0..self.height).rev() {
+            for x in (0..w).rev() {
+                let down = (y + 1 < self.height).then(|| (y + 1) * w + x);
+                jump(y * w + x, Dir4::Down, down);
+                jump(y * w + x, Dir4::Right, (x + 1 < w).then(|| y * w + x + 1));
             }
         }
     }
 
-    fn rotate(&mut self) {
-        self.direction = match self.direction {
-            (0, -1) => (1, 0),
-            (1, 0) => (0, 1),
-            (0, 1) => (-1, 0),
-            (-1, 0) => (0, -1),
-            _ => (0, 0),
+    /// The neighbouring cell in `direction`, or `None` at the edge of the lab.
+    fn step(&self, cell: usize, direction: Dir4) -> Option<usize> {
+        let (x, y) = (cell % self.width, cell / self.width);
+        match direction {
+            Dir4::Up => (y > 0).then(|| cell - self.width),
+            Dir4::Right => (x + 1 < self.width).then(|| cell + 1),
+            Dir4::Down => (y + 1 < self.height).then(|| cell + self.width),
+            Dir4::Left => (x > 0).then(|| cell - 1),
         }
     }
 
-    fn solve(&mut self) {
-        while (!self.done) && (!self.looped) {
-            self.step();
+    /// Walks the guard's patrol cell by cell and returns every cell she enters for the first
+    /// time, or `None` if she never leaves. `on_turn` is called whenever she turns, and once more
+    /// before she leaves.
+    fn patrol(&self, mut on_turn: impl FnMut(&[bool], usize, Dir4)) -> Option<Vec<Entry>> {
+        let mut visited = vec![false; self.blocked.len()];
+        visited[self.start] = true;
+        let mut turns = Visited::new(self.blocked.len() * 4);
+        let mut entries = vec![];
+        let (mut cell, mut direction) = (self.start, Dir4::Up);
+
+        while let Some(next) = self.step(cell, direction) {
+            if self.blocked[next] {
+                direction = direction.turn_right();
+                // every loop has a turn, so turning the same way twice means she is stuck.
+                if !turns.insert(cell * 4 + direction as usize) {
+                    return None;
+                }
+                on_turn(&visited, cell, direction);
+            } else {
+                if !visited[next] {
+                    visited[next] = true;
+                    entries.push(Entry {
+                        cell: next,
+                        from: cell,
+                        direction,
+                    });
+                }
+                cell = next;
+            }
         }
-    }
 
-    fn get_locs(&mut self) -> HashSet<(S, S)> {
-        self.solve();
-        self.visited.iter().map(|f| f.0).collect()
+        on_turn(&visited, cell, direction);
+        Some(entries)
     }
-}
 
-impl FromStr for Guard {
-    type Err = ();
+    /// The cell where the guard stops when walking from `cell` with an added `obstacle`, or
+    /// `None` if she leaves the lab.
+    fn jump(&self, cell: usize, direction: Dir4, obstacle: usize) -> Option<usize> {
+        let w = self.width;
+        let (x, y) = (cell % w, cell / w);
+        let (ox, oy) = (obstacle % w, obstacle / w);
+        let before_obstacle = match direction {
+            Dir4::Up => (ox == x && oy < y).then(|| obstacle + w),
+            Dir4::Right => (oy == y && ox > x).then(|| obstacle - 1),
+            Dir4::Down => (ox == x && oy > y).then(|| obstacle - w),
+            Dir4::Left => (oy == y && ox < x).then(|| obstacle + 1),
+        };
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let x: S = s.find("\n").unwrap() as S;
-        let y: S = (s.len() as S / (x + 1)) as S;
+        let target = self.jumps[cell][direction as usize];
+        match before_obstacle {
+            // both lie ahead in the same row or column, so the nearer one is closer in index.
+            Some(stop)
+                if target == EXIT || stop.abs_diff(cell) < (target as usize).abs_diff(cell) =>
+            {
+                Some(stop)
+            }
+            _ => (target != EXIT).then_some(target as usize),
+        }
+    }
 
-        let mut obstacles = HashSet::new();
-        let mut start: (S, S) = (0, 0);
+    /// Whether the guard walks in a loop after `entry` is blocked, starting in front of it.
+    fn loops(&self, entry: Entry, seen: &mut Visited) -> bool {
+        let (mut cell, mut direction) = (entry.from, entry.direction);
+        let looped = loop {
+            let Some(stop) = self.jump(cell, direction, entry.cell) else {
+                break false;
+            };
+            cell = stop;
+            direction = direction.turn_right();
+            if !seen.insert(cell * 4 + direction as usize) {
+                break true;
+            }
+        };
+        seen.clear();
+        looped
+    }
 
-        for (j, line) in s.lines().enumerate() {
-            for (i, c) in line.chars().enumerate() {
-                match c {
-                    '#' => {
-                        obstacles.insert((i as S, j as S));
-                    }
-                    '^' => start = (i as S, j as S),
-                    _ => {}
-                }
+    fn count_loops(&self, entries: &[Entry]) -> usize {
+        let mut seen = Visited::new(self.blocked.len() * 4);
+        entries
+            .iter()
+            .filter(|&&entry| self.loops(entry, &mut seen))
+            .count()
+    }
+
+    fn frame(&self, visited: &[bool], guard: usize, direction: Dir4) -> Frame {
+        let mut frame = Frame::new(self.width, self.height, Cell::from('.'));
+        for (i, (&blocked, &visited)) in self.blocked.iter().zip(visited).enumerate() {
+            let pos = (i % self.width, i / self.width);
+            if blocked {
+                frame[pos] = Cell::from('#');
+            } else if visited {
+                frame[pos] = Cell::new('X', Rgb::YELLOW);
             }
         }
-
-        Ok(Self::from_start(start, (0..x, 0..y), obstacles))
+        let glyph = match direction {
+            Dir4::Up => '^',
+            Dir4::Right => '>',
+            Dir4::Down => 'v',
+            Dir4::Left => '<',
+        };
+        frame[(guard % self.width, guard / self.width)] = Cell::new(glyph, Rgb::RED);
+        frame
     }
 }
 
-pub fn part_one(input: &str) -> Option<S> {
-    let mut guard = Guard::from_str(input).unwrap();
-    let locs = guard.get_locs();
-    Some(locs.len() as S)
+/// A dense set of states, which remembers what it set to clear only that again.
+struct Visited {
+    bits: Vec<u64>,
+    set: Vec<usize>,
 }
 
-pub fn part_two(input: &str) -> Option<S> {
-    let mut guard = Guard::from_str(input).unwrap();
-    let mut locs = guard.get_locs();
+impl Visited {
+    fn new(len: usize) -> Self {
+        Self {
+            bits: vec![0; len.div_ceil(64)],
+            set: vec![],
+        }
+    }
 
-    locs.remove(&(guard.start));
+    /// Adds `index` and returns whether it was new.
+    fn insert(&mut self, index: usize) -> bool {
+        let (word, bit) = (index / 64, 1 << (index % 64));
+        if self.bits[word] & bit != 0 {
+            return false;
+        }
+        self.bits[word] |= bit;
+        self.set.push(index);
+        true
+    }
 
-    let mut loop_count: S = 0;
+    fn clear(&mut self) {
+        for index in self.set.drain(..) {
+            self.bits[index / 64] = 0;
+        }
+    }
+}
 
-    for obs in locs {
-        guard.reset();
-        guard.obstacles.insert(obs);
-        guard.solve();
+pub fn part_one(input: &str) -> Option<usize> {
+    let lab = Lab::parse(input)?;
+    let mut recorder = Recorder::new(DAY, "guard");
+    let entries = lab.patrol(|visited, guard, direction| {
+        recorder.frame(|| lab.frame(visited, guard, direction));
+    })?;
+    recorder.finish();
+    // the start is never entered.
+    Some(entries.len() + 1)
+}
 
-        if guard.looped {
-            loop_count += 1
-        }
+pub fn part_two(input: &str) -> Option<usize> {
+    let lab = Lab::parse(input)?;
+    let entries = lab.patrol(|_, _, _| {})?;
+    Some(lab.count_loops(&entries))
+}
 
-        guard.obstacles.remove(&obs);
-    }
+pub fn part_two_parallel(input: &str) -> Option<usize> {
+    let lab = Lab::parse(input)?;
+    let entries = lab.patrol(|_, _, _| {})?;
+    let threads = thread::available_parallelism().map_or(1, usize::from);
+    let chunk = entries.len().div_ceil(threads).max(1);
 
-    Some(loop_count)
+    thread::scope(|scope| {
+        let workers: Vec<_> = entries
+            .chunks(chunk)
+            .map(|entries| scope.spawn(|| lab.count_loops(entries)))
+            .collect();
+        let loops = workers
+            .into_iter()
+            .map(|worker| worker.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .sum();
+        Some(loops)
+    })
 }
 
 #[cfg(test)]
@@ -146,4 +272,17 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(6));
     }
+
+    #[test]
+    fn test_endless_patrol() {
+        let input = ".#...\n.^..#\n.....\n#....\n...#.\n";
+        assert_eq!(part_one(input), None);
+        assert_eq!(part_two(input), None);
+    }
+
+    #[test]
+    fn test_part_two_parallel() {
+        let result = part_two_parallel(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(6));
+    }
 }
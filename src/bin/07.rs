@@ -1,7 +1,9 @@
 advent_of_code::solution!(7);
 
+use advent_of_code::math::Digits;
+use advent_of_code::parse::{equation, lines, parse, ParseError};
+
 type S = u64;
-const TEN: S = 10;
 
 #[derive(Debug)]
 struct Equation {
@@ -10,15 +12,12 @@ struct Equation {
 }
 
 impl Equation {
-    fn from_lines(s: &str) -> Vec<Self> {
-        s.lines()
-            .map(|line| {
-                let mut i = line.split([' ', ':']).filter(|f| !f.is_empty());
-                let target: S = i.next().unwrap().parse().unwrap();
-                let components: Vec<S> = i.map(|f| f.parse().unwrap()).collect();
-                Self { target, components }
-            })
-            .collect()
+    fn from_lines(s: &str) -> Result<Vec<Self>, ParseError> {
+        let equations: Vec<(S, Vec<S>)> = parse(s, lines(equation))?;
+        Ok(equations
+            .into_iter()
+            .map(|(target, components)| Self { target, components })
+            .collect())
     }
 
     fn solveable(&self) -> bool {
@@ -48,35 +47,27 @@ impl Equation {
         } else {
             Self::dfs_concat(target, buffer + remaining[0], &remaining[1..])
                 || Self::dfs_concat(target, buffer * remaining[0], &remaining[1..])
-                || Self::dfs_concat(target, concat(buffer, remaining[0]), &remaining[1..])
+                || Self::dfs_concat(target, buffer.concat(remaining[0]), &remaining[1..])
         }
     }
 }
 
-fn concat(l: S, r: S) -> S {
-    l * TEN.pow(r.ilog10() + 1) + r
-}
-
-pub fn part_one(input: &str) -> Option<S> {
-    let v = Equation::from_lines(input);
+pub fn part_one(input: &str) -> Result<S, ParseError> {
+    let v = Equation::from_lines(input)?;
 
-    Some(
-        v.into_iter()
-            .filter(|f| f.solveable())
-            .map(|f| f.target)
-            .sum(),
-    )
+    Ok(v.into_iter()
+        .filter(|f| f.solveable())
+        .map(|f| f.target)
+        .sum())
 }
 
-pub fn part_two(input: &str) -> Option<S> {
-    let v = Equation::from_lines(input);
+pub fn part_two(input: &str) -> Result<S, ParseError> {
+    let v = Equation::from_lines(input)?;
 
-    Some(
-        v.into_iter()
-            .filter(|f| f.solveable_concat())
-            .map(|f| f.target)
-            .sum(),
-    )
+    Ok(v.into_iter()
+        .filter(|f| f.solveable_concat())
+        .map(|f| f.target)
+        .sum())
 }
 
 #[cfg(test)]
@@ -86,12 +77,23 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3749));
+        assert_eq!(result, Ok(3749));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11387));
+        assert_eq!(result, Ok(11387));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let result = part_one("190: 10 19\n3267: 81 x 27\n");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 2, column 10: expected unsigned integer, found `x`\n\
+             2 | 3267: 81 x 27\n  \
+               |          ^"
+        );
     }
 }
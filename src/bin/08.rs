@@ -1,61 +1,45 @@
 advent_of_code::solution!(8);
+use advent_of_code::point::Point;
+use advent_of_code::viz::{Cell, Frame, Recorder, Rgb};
 use itertools::Itertools;
 use std::{ops::Range, str::FromStr};
 
 type S = i16;
 #[derive(Debug, Clone, Copy)]
 struct Antenna {
-    x: S,
-    y: S,
+    pos: Point<S>,
     variant: char,
 }
 
 impl Antenna {
+    fn at(self, pos: Point<S>) -> Self {
+        Self { pos, ..self }
+    }
+
     fn antinode_pair(self, other: Self) -> Vec<Self> {
-        let offset_x = other.x - self.x;
-        let offset_y = other.y - self.y;
-
-        vec![
-            Self {
-                x: other.x + offset_x,
-                y: other.y + offset_y,
-                variant: self.variant,
-            },
-            Self {
-                x: self.x - offset_x,
-                y: self.y - offset_y,
-                variant: self.variant,
-            },
-        ]
+        let offset = other.pos - self.pos;
+
+        vec![self.at(other.pos + offset), self.at(self.pos - offset)]
     }
 
     fn antinode_array(self, other: Self, x_domain: &Range<S>, y_domain: &Range<S>) -> Vec<Self> {
-        let offset_x = other.x - self.x;
-        let offset_y = other.y - self.y;
+        let offset = other.pos - self.pos;
 
-        let oth_x = (x_domain.end - other.x.max(self.x)) / offset_x;
-        let oth_y = (y_domain.end - other.y.max(self.y)) / offset_y;
+        let oth_x = (x_domain.end - other.pos.x.max(self.pos.x)) / offset.x;
+        let oth_y = (y_domain.end - other.pos.y.max(self.pos.y)) / offset.y;
 
         let oth_n = oth_x.max(oth_y);
 
-        let self_x = self.x.min(other.x) / offset_x;
-        let self_y = self.y.min(other.y) / offset_y;
+        let self_x = self.pos.x.min(other.pos.x) / offset.x;
+        let self_y = self.pos.y.min(other.pos.y) / offset.y;
 
         let self_n = self_x.max(self_y);
 
         let mut s: Vec<Self> = (0..=self_n)
-            .map(|n| Self {
-                x: self.x - n * offset_x,
-                y: self.y - n * offset_y,
-                variant: self.variant,
-            })
+            .map(|n| self.at(self.pos - offset * n))
             .collect();
 
-        s.extend((0..=oth_n).map(|n| Self {
-            x: other.x + n * offset_x,
-            y: other.y + n * offset_y,
-            variant: self.variant,
-        }));
+        s.extend((0..=oth_n).map(|n| self.at(other.pos + offset * n)));
 
         s
     }
@@ -68,7 +52,7 @@ struct Field {
 }
 
 impl Field {
-    fn get_antinodes(&mut self) -> Vec<Antenna> {
+    fn get_antinodes(&mut self, recorder: &mut Recorder) -> Vec<Antenna> {
         let mut antinodes: Vec<Antenna> = Vec::new();
 
         self.antennae.sort_by_key(|a| a.variant);
@@ -76,15 +60,16 @@ impl Field {
             antinodes.extend(
                 group
                     .combinations(2)
-                    .flat_map(|f| ((f[0]).antinode_pair(*f[1])))
+                    .flat_map(|f| (f[0]).antinode_pair(*f[1]))
                     .filter(|f| self.validate_node(f)),
             );
+            recorder.frame(|| self.frame(&antinodes));
         }
 
         antinodes
     }
 
-    fn get_all_antinodes(&mut self) -> Vec<Antenna> {
+    fn get_all_antinodes(&mut self, recorder: &mut Recorder) -> Vec<Antenna> {
         let mut antinodes: Vec<Antenna> = Vec::new();
 
         self.antennae.sort_by_key(|a| a.variant);
@@ -92,16 +77,30 @@ impl Field {
             antinodes.extend(
                 group
                     .combinations(2)
-                    .flat_map(|f| ((f[0]).antinode_array(*f[1], &self.x_domain, &self.y_domain)))
+                    .flat_map(|f| (f[0]).antinode_array(*f[1], &self.x_domain, &self.y_domain))
                     .filter(|f| self.validate_node(f)),
             );
+            recorder.frame(|| self.frame(&antinodes));
         }
 
         antinodes
     }
 
     fn validate_node(&self, node: &Antenna) -> bool {
-        self.x_domain.contains(&node.x) && self.y_domain.contains(&node.y)
+        self.x_domain.contains(&node.pos.x) && self.y_domain.contains(&node.pos.y)
+    }
+
+    /// The antennas over their antinodes so far, antinodes in white.
+    fn frame(&self, antinodes: &[Antenna]) -> Frame {
+        let (width, height) = (self.x_domain.end as usize, self.y_domain.end as usize);
+        let mut frame = Frame::new(width, height, Cell::from('.'));
+        for antinode in antinodes {
+            frame[antinode.pos] = Cell::new('#', Rgb::WHITE);
+        }
+        for antenna in &self.antennae {
+            frame[antenna.pos] = Cell::from(antenna.variant);
+        }
+        frame
     }
 }
 
@@ -117,8 +116,7 @@ impl FromStr for Field {
             .enumerate()
             .filter(|(_, c)| *c != '.')
             .map(|(i, c)| Antenna {
-                x: i as S % w,
-                y: i as S / w,
+                pos: Point::new(i as S % w, i as S / w),
                 variant: c,
             })
             .collect();
@@ -134,11 +132,13 @@ impl FromStr for Field {
 pub fn part_one(input: &str) -> Option<usize> {
     let mut f = Field::from_str(input).unwrap();
 
-    let mut an = f.get_antinodes();
+    let mut recorder = Recorder::new(DAY, "antinodes");
+    let mut an = f.get_antinodes(&mut recorder);
+    recorder.finish();
 
-    an.sort_by_key(|f| (f.y, f.x));
+    an.sort_by_key(|f| (f.pos.y, f.pos.x));
 
-    an.dedup_by_key(|f| (f.y, f.x));
+    an.dedup_by_key(|f| (f.pos.y, f.pos.x));
 
     Some(an.len())
 }
@@ -146,11 +146,13 @@ pub fn part_one(input: &str) -> Option<usize> {
 pub fn part_two(input: &str) -> Option<usize> {
     let mut f = Field::from_str(input).unwrap();
 
-    let mut an = f.get_all_antinodes();
+    let mut recorder = Recorder::new(DAY, "harmonics");
+    let mut an = f.get_all_antinodes(&mut recorder);
+    recorder.finish();
 
-    an.sort_by_key(|f| (f.y, f.x));
+    an.sort_by_key(|f| (f.pos.y, f.pos.x));
 
-    an.dedup_by_key(|f| (f.y, f.x));
+    an.dedup_by_key(|f| (f.pos.y, f.pos.x));
 
     Some(an.len())
 }
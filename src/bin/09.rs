@@ -154,6 +154,7 @@ pub fn part_two(input: &str) -> Option<S> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::prop::{self, Rng};
 
     #[test]
     fn test_part_one() {
@@ -166,4 +167,57 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(2858));
     }
+
+    /// Alternating file and gap lengths, starting and ending with a file.
+    fn generate_disk_map(rng: &mut Rng) -> String {
+        let files = rng.range(1..=rng.size());
+        let mut map = String::new();
+        for i in 0..files {
+            if i > 0 {
+                map.push(char::from(b'0' + rng.range(0..=9u8)));
+            }
+            map.push(char::from(b'0' + rng.range(1..=9u8)));
+        }
+        map + "\n"
+    }
+
+    /// Expands the disk block by block and moves the last block into the first gap.
+    fn reference_part_one(input: &str) -> Option<S> {
+        let mut disk: Vec<Option<S>> = vec![];
+        for (i, len) in parse_input(input).into_iter().enumerate() {
+            let id = (i % 2 == 0).then_some(i / 2);
+            disk.extend(std::iter::repeat_n(id, len.into()));
+        }
+
+        let (mut left, mut right) = (0, disk.len() - 1);
+        while left < right {
+            if disk[left].is_some() {
+                left += 1;
+            } else if disk[right].is_none() {
+                right -= 1;
+            } else {
+                disk.swap(left, right);
+            }
+        }
+
+        Some(
+            disk.iter()
+                .enumerate()
+                .map(|(i, id)| i * id.unwrap_or(0))
+                .sum(),
+        )
+    }
+
+    #[test]
+    fn test_part_one_agrees_with_reference() {
+        prop::check(
+            generate_disk_map,
+            prop::agrees_with(part_one, reference_part_one),
+        );
+    }
+
+    #[test]
+    fn test_part_two_never_panics() {
+        prop::check(generate_disk_map, prop::never_panics(part_two));
+    }
 }
@@ -1,34 +1,39 @@
 advent_of_code::solution!(11);
 
-use cached::proc_macro::cached;
+use advent_of_code::math::Digits;
+use advent_of_code::memo::Memo;
+use advent_of_code::{trace, trace_scope};
 
 use counter::Counter;
 
 type S = usize;
 
-#[cached]
-fn blink_pebble(value: S) -> Counter<S> {
-    match value {
+type BlinkMemo = Memo<S, Counter<S>>;
+
+fn blink_pebble(memo: &mut BlinkMemo, value: S) -> Counter<S> {
+    memo.get_or_insert_with(value, |_| match value {
         0 => Counter::from_iter(vec![1]),
-        a if a.ilog10() % 2 == 1 => {
-            let fac = (10 as S).pow(a.ilog10().div_ceil(2));
-            Counter::from_iter(vec![a / fac, a - (a / fac) * fac])
+        a if a.num_digits() % 2 == 0 => {
+            let (left, right) = a.split_digits(a.num_digits() / 2);
+            Counter::from_iter(vec![left, right])
         }
         _ => Counter::from_iter(vec![value * 2024]),
-    }
+    })
 }
 
-fn blink(pebbles: Counter<S>) -> Counter<S> {
+fn blink(memo: &mut BlinkMemo, pebbles: Counter<S>) -> Counter<S> {
+    trace_scope!("blink", "{} distinct pebbles", pebbles.len());
     let mut out: Counter<S> = Counter::new();
 
     for (val, count) in pebbles {
-        let mut c = blink_pebble(val);
+        let mut c = blink_pebble(memo, val);
         c.iter_mut().for_each(|(_, val)| {
             *val *= count;
         });
         out += c;
     }
 
+    trace!("memo", "{} values cached", memo.len());
     out
 }
 
@@ -40,20 +45,22 @@ fn parse_input(s: &str) -> Counter<S> {
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
+    let mut memo = Memo::new();
     let mut pebbles = parse_input(input);
 
     for _ in 0..25 {
-        pebbles = blink(pebbles);
+        pebbles = blink(&mut memo, pebbles);
     }
 
     Some(pebbles.into_iter().map(|(_, c)| c).sum())
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
+    let mut memo = Memo::new();
     let mut pebbles = parse_input(input);
 
     for _ in 0..75 {
-        pebbles = blink(pebbles);
+        pebbles = blink(&mut memo, pebbles);
     }
 
     Some(pebbles.into_iter().map(|(_, c)| c).sum())
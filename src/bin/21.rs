@@ -2,8 +2,8 @@ advent_of_code::solution!(21);
 
 use std::fmt::Debug;
 
+use advent_of_code::memo::Memo;
 use itertools::Itertools;
-use memoize::memoize;
 use num_enum::IntoPrimitive;
 
 type S = usize;
@@ -131,20 +131,23 @@ impl GetCoord for DirectionKey {
     const NP: (u8, u8) = (0, 1);
 }
 
-#[memoize]
-fn recursive_search(start: DirectionKey, end: DirectionKey, depth: u8) -> S {
-    dispatch_seq(start, end, depth)
+type SearchMemo = Memo<(DirectionKey, DirectionKey, u8), S>;
+
+fn recursive_search(memo: &mut SearchMemo, start: DirectionKey, end: DirectionKey, depth: u8) -> S {
+    memo.get_or_insert_with((start, end, depth), |memo| {
+        dispatch_seq(memo, start, end, depth)
+    })
 }
 
 // Indirection needed as NumericKey and DirectionKey are different types so cannot share cache
-fn numeric_length(seq: Vec<NumericKey>, depth: u8) -> usize {
+fn numeric_length(memo: &mut SearchMemo, seq: Vec<NumericKey>, depth: u8) -> usize {
     seq.into_iter()
         .tuple_windows()
-        .map(|(start, end)| dispatch_seq(start, end, depth))
+        .map(|(start, end)| dispatch_seq(memo, start, end, depth))
         .sum()
 }
 
-fn dispatch_seq<T: GetCoord>(start: T, end: T, depth: u8) -> usize {
+fn dispatch_seq<T: GetCoord>(memo: &mut SearchMemo, start: T, end: T, depth: u8) -> usize {
     let (mut seq, order) = T::get_paths(start, end);
     if depth == 0 {
         seq.len() + 1
@@ -158,7 +161,7 @@ fn dispatch_seq<T: GetCoord>(start: T, end: T, depth: u8) -> usize {
 
                 seq.into_iter()
                     .tuple_windows()
-                    .map(|(start, end)| recursive_search(start, end, depth - 1))
+                    .map(|(start, end)| recursive_search(memo, start, end, depth - 1))
                     .sum()
             }
             PathOrder::Both => {
@@ -172,13 +175,13 @@ fn dispatch_seq<T: GetCoord>(start: T, end: T, depth: u8) -> usize {
                 let forward_sum: S = seq
                     .into_iter()
                     .tuple_windows()
-                    .map(|(start, end)| recursive_search(start, end, depth - 1))
+                    .map(|(start, end)| recursive_search(memo, start, end, depth - 1))
                     .sum();
 
                 let backward_sum: S = backwards
                     .into_iter()
                     .tuple_windows()
-                    .map(|(start, end)| recursive_search(start, end, depth - 1))
+                    .map(|(start, end)| recursive_search(memo, start, end, depth - 1))
                     .sum();
                 forward_sum.min(backward_sum)
             }
@@ -187,18 +190,24 @@ fn dispatch_seq<T: GetCoord>(start: T, end: T, depth: u8) -> usize {
 }
 
 pub fn part_one(input: &str) -> Option<S> {
-    memoized_flush_recursive_search();
+    let mut memo = Memo::new();
     let (values, inputs) = parse_input(input);
-    let s: Vec<S> = inputs.into_iter().map(|v| numeric_length(v, 2)).collect();
+    let s: Vec<S> = inputs
+        .into_iter()
+        .map(|v| numeric_length(&mut memo, v, 2))
+        .collect();
     let p: S = s.into_iter().zip(values).map(|(l, r)| l * r).sum();
 
     Some(p)
 }
 
 pub fn part_two(input: &str) -> Option<S> {
-    memoized_flush_recursive_search();
+    let mut memo = Memo::new();
     let (values, inputs) = parse_input(input);
-    let s: Vec<S> = inputs.into_iter().map(|v| numeric_length(v, 25)).collect();
+    let s: Vec<S> = inputs
+        .into_iter()
+        .map(|v| numeric_length(&mut memo, v, 25))
+        .collect();
     let p: S = s.into_iter().zip(values).map(|(l, r)| l * r).sum();
 
     Some(p)
@@ -1,8 +1,9 @@
+use advent_of_code::parse::{lines, parse, ParseError};
 use counter::Counter;
-use std::{num::ParseIntError, str::FromStr};
 use unroll::unroll_for_loops;
+use winnow::ascii::dec_uint;
 
-advent_of_code::solution!(22);
+advent_of_code::solution!(22, parse: parse_secrets);
 
 type S = u64;
 type C = i32;
@@ -74,35 +75,32 @@ impl SecretNumber {
     }
 }
 
-impl FromStr for SecretNumber {
-    type Err = ParseIntError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let value = s.parse()?;
-        Ok(Self {
+impl SecretNumber {
+    fn new(value: S) -> Self {
+        Self {
             value,
             price: Vec::with_capacity(2000),
             delta: Vec::with_capacity(2000),
             patterns: Counter::with_capacity(500),
-        })
+        }
     }
 }
 
-pub fn part_one(input: &str) -> Option<S> {
-    let try_parse: Result<Vec<SecretNumber>, ParseIntError> =
-        input.lines().map(SecretNumber::from_str).collect();
+fn parse_secrets(input: &str) -> Result<Vec<SecretNumber>, ParseError> {
+    let values: Vec<S> = parse(input.as_bytes(), lines(dec_uint))?;
+    Ok(values.into_iter().map(SecretNumber::new).collect())
+}
 
-    let mut secrets = try_parse.unwrap();
+pub fn part_one(input: &str) -> Result<S, ParseError> {
+    let mut secrets = parse_secrets(input)?;
 
     secrets.iter_mut().for_each(|f| f.update());
 
-    Some(secrets.into_iter().map(|f| f.value).sum())
+    Ok(secrets.into_iter().map(|f| f.value).sum())
 }
 
-pub fn part_two(input: &str) -> Option<C> {
-    let try_parse: Result<Vec<SecretNumber>, ParseIntError> =
-        input.lines().map(SecretNumber::from_str).collect();
-
-    let mut secrets = try_parse.unwrap();
+pub fn part_two(input: &str) -> Result<C, ParseError> {
+    let mut secrets = parse_secrets(input)?;
 
     secrets.iter_mut().for_each(|f| {
         f.update_tracked();
@@ -115,7 +113,7 @@ pub fn part_two(input: &str) -> Option<C> {
         .map(|f| f.patterns)
         .fold(Counter::new(), |acc, x| acc + x);
 
-    Some(total_bananas.most_common()[0].1)
+    Ok(total_bananas.most_common()[0].1)
 }
 
 #[cfg(test)]
@@ -128,7 +126,7 @@ mod tests {
             "examples", DAY, 1,
         ));
 
-        assert_eq!(result, Some(37327623));
+        assert_eq!(result, Ok(37327623));
     }
 
     #[test]
@@ -136,6 +134,6 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file_part(
             "examples", DAY, 2,
         ));
-        assert_eq!(result, Some(23));
+        assert_eq!(result, Ok(23));
     }
 }
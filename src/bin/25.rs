@@ -1,8 +1,10 @@
 use std::ops::BitOr;
 
+use advent_of_code::grid::Grid;
+use advent_of_code::parse::{blocks, grid, parse, ParseError};
 use itertools::Itertools;
 
-advent_of_code::solution!(25);
+advent_of_code::solution!(25, parse: parse_input);
 
 type S = usize;
 const SIXTEEN: S = 16;
@@ -39,23 +41,22 @@ impl BitOr for &HeightMap {
 }
 
 impl HeightMap {
-    fn from_schematic(input: &str) -> Self {
-        let variant = match input.chars().nth(0) {
-            Some('#') => EntityType::Lock,
-            Some('.') => EntityType::Key,
+    fn from_schematic(schematic: &Grid<u8>) -> Self {
+        let variant = match schematic.get((0, 0)) {
+            Some(b'#') => EntityType::Lock,
+            Some(b'.') => EntityType::Key,
             _ => panic!("Invalid schematic"),
         };
 
-        let l: Vec<&str> = match variant {
-            EntityType::Key => input.lines().rev().skip(1).collect(),
-            EntityType::Lock => input.lines().skip(1).collect(),
+        let l: Vec<&[u8]> = match variant {
+            EntityType::Key => schematic.rows().take(schematic.height() - 1).collect(),
+            EntityType::Lock => schematic.rows().skip(1).collect(),
         };
 
         let heights = l
             .into_iter()
             .map(|f| {
-                f.as_bytes()
-                    .iter()
+                f.iter()
                     .enumerate()
                     .map(|(i, f)| SIXTEEN.pow(i as u32) * (1 - ((*f - 35) / 11)) as S)
                     .sum::<S>()
@@ -66,22 +67,22 @@ impl HeightMap {
     }
 }
 
-fn parse_input(input: &str) -> (Vec<HeightMap>, Vec<HeightMap>) {
-    input
-        .split("\n\n")
+fn parse_input(input: &str) -> Result<(Vec<HeightMap>, Vec<HeightMap>), ParseError> {
+    let schematics = parse(input.as_bytes(), blocks(grid))?;
+    Ok(schematics
+        .iter()
         .map(HeightMap::from_schematic)
-        .partition(|f| matches!(f.variant, EntityType::Key))
+        .partition(|f| matches!(f.variant, EntityType::Key)))
 }
 
-pub fn part_one(input: &str) -> Option<S> {
-    let (keys, locks) = parse_input(input);
+pub fn part_one(input: &str) -> Result<S, ParseError> {
+    let (keys, locks) = parse_input(input)?;
 
-    Some(
-        keys.into_iter()
-            .cartesian_product(locks)
-            .filter(|(key, lock)| key | lock)
-            .count(),
-    )
+    Ok(keys
+        .into_iter()
+        .cartesian_product(locks)
+        .filter(|(key, lock)| key | lock)
+        .count())
 }
 
 #[cfg(test)]
@@ -91,6 +92,6 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3));
+        assert_eq!(result, Ok(3));
     }
 }
@@ -0,0 +1,208 @@
+/// Cycle detection for simulations that repeatedly apply a `step` function to a state.
+///
+/// The sequence `initial, step(initial), step(step(initial)), ...` of a deterministic
+/// simulation over finitely many states always ends up in a cycle. [`brent`] and [`floyd`]
+/// find it in constant memory, [`hashed`] in a single pass by remembering every state.
+///
+/// Simulations that can end, e.g. a guard leaving the map, can be modelled by stepping into a
+/// final state that steps to itself, like `None`. The detected cycle is then that state.
+use std::hash::Hash;
+
+use rustc_hash::FxHashMap;
+
+/// A cycle of a sequence of states.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<S> {
+    /// The number of steps until the cycle is entered for the first time.
+    pub start: usize,
+    /// The number of steps it takes to go around the cycle once.
+    pub length: usize,
+    /// The first state of the cycle, reached after `start` steps.
+    pub state: S,
+}
+
+impl<S> Cycle<S> {
+    /// The smallest number of steps that ends in the same state as taking `n` steps.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Finds the cycle with Brent's algorithm, which usually steps less often than [`floyd`].
+pub fn brent<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle<S> {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // With the hare `length` steps ahead, both meet at the start of the cycle.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle {
+        start,
+        length,
+        state: tortoise,
+    }
+}
+
+/// Finds the cycle with Floyd's algorithm ("tortoise and hare").
+pub fn floyd<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle<S> {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    let mut tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle {
+        start,
+        length,
+        state: tortoise,
+    }
+}
+
+/// Finds the cycle by remembering every state, so that each state is stepped exactly once.
+/// Best for states that are cheap to hash but expensive to step.
+pub fn hashed<S: Clone + Eq + Hash>(initial: S, step: impl FnMut(&S) -> S) -> Cycle<S> {
+    let (states, start) = run_until_repeat(initial, step);
+    Cycle {
+        start,
+        length: states.len() - start,
+        state: states[start].clone(),
+    }
+}
+
+/// The state after `n` steps, skipping over whole cycles once one is found, e.g. to simulate a
+/// billion rounds.
+pub fn nth<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let mut seen = FxHashMap::default();
+    let mut states = Vec::new();
+    let mut state = initial;
+
+    while states.len() < n {
+        if let Some(&start) = seen.get(&state) {
+            let length = states.len() - start;
+            return states.swap_remove(start + (n - start) % length);
+        }
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+
+    state
+}
+
+/// Steps until a state repeats. Returns all distinct states in order and the index of the
+/// repeated one.
+fn run_until_repeat<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+) -> (Vec<S>, usize) {
+    let mut seen = FxHashMap::default();
+    let mut states = Vec::new();
+    let mut state = initial;
+
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            return (states, start);
+        }
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{brent, floyd, hashed, nth, Cycle};
+
+    /// 0, 1, 2, then 3, 4, 5, 6, 7 repeating.
+    fn step(x: &u32) -> u32 {
+        if *x == 7 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn finds_cycles() {
+        let expected = Cycle {
+            start: 3,
+            length: 5,
+            state: 3,
+        };
+        assert_eq!(brent(0, step), expected);
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(hashed(0, step), expected);
+    }
+
+    #[test]
+    fn finds_fixed_points() {
+        let expected = Cycle {
+            start: 4,
+            length: 1,
+            state: None,
+        };
+        let step = |x: &Option<u8>| x.and_then(|x| x.checked_sub(1));
+        assert_eq!(brent(Some(3), step), expected);
+        assert_eq!(floyd(Some(3), step), expected);
+        assert_eq!(hashed(Some(3), step), expected);
+    }
+
+    #[test]
+    fn skips_cycles() {
+        let naive = |n: usize| (0..n).fold(0, |x, _| step(&x));
+        for n in 0..30 {
+            assert_eq!(nth(0, step, n), naive(n));
+            assert_eq!(naive(brent(0, step).reduce(n)), naive(n));
+        }
+        assert_eq!(nth(0, step, 1_000_000_000), 3 + (1_000_000_000 - 3) % 5);
+    }
+}
@@ -0,0 +1,415 @@
+/// A dense 2D grid, as used by most map-based puzzles.
+///
+/// Positions are [`Point`]s with the origin in the top-left corner, `x` growing to the right and
+/// `y` growing downwards.
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::point::{Dir4, Dir8, Point, Vec2};
+
+/// A position in a [`Grid`].
+pub type Pos = Point<usize>;
+
+/// Anything that can index a [`Grid`]: a [`Point`] or an `(x, y)` pair of any integer type.
+///
+/// Negative or otherwise out-of-range coordinates are simply outside of the grid, so signed
+/// points can be used without converting them first.
+pub trait GridIndex: Copy {
+    fn to_pos(self) -> Option<Pos>;
+}
+
+impl<T: TryInto<usize> + Copy> GridIndex for Point<T> {
+    fn to_pos(self) -> Option<Pos> {
+        Some(Point::new(self.x.try_into().ok()?, self.y.try_into().ok()?))
+    }
+}
+
+impl<T: TryInto<usize> + Copy> GridIndex for (T, T) {
+    fn to_pos(self) -> Option<Pos> {
+        Point::from(self).to_pos()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells in row-major order.
+    ///
+    /// # Panics
+    /// Panics if the number of cells is not a multiple of `width`.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            cells.len().is_multiple_of(width),
+            "{} cells do not fit into rows of width {width}.",
+            cells.len()
+        );
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a grid with one row per line, mapping every byte to a cell with `f`.
+    pub fn parse_with(input: &str, mut f: impl FnMut(u8) -> T) -> Result<Self, GridError> {
+        let width = input.lines().next().map_or(0, str::len);
+        let mut cells = Vec::with_capacity(input.len());
+
+        for (y, line) in input.lines().enumerate() {
+            if line.len() != width {
+                return Err(GridError::Ragged {
+                    line: y + 1,
+                    expected: width,
+                    found: line.len(),
+                });
+            }
+            cells.extend(line.bytes().map(&mut f));
+        }
+
+        Ok(Self::from_vec(width, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: impl GridIndex) -> bool {
+        self.index_of(pos).is_some()
+    }
+
+    fn index_of(&self, pos: impl GridIndex) -> Option<usize> {
+        let Point { x, y } = pos.to_pos()?;
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    pub fn get(&self, pos: impl GridIndex) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: impl GridIndex) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Moves `pos` by `delta`, e.g. a [`Dir4`], returning [`None`] if that leaves the grid.
+    pub fn offset(&self, pos: Pos, delta: impl Into<Vec2<isize>>) -> Option<Pos> {
+        let delta = delta.into();
+        let pos = Point::new(
+            pos.x.checked_add_signed(delta.x)?,
+            pos.y.checked_add_signed(delta.y)?,
+        );
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The orthogonal neighbours of `pos` within the grid, clockwise starting upwards.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |dir| self.offset(pos, dir))
+    }
+
+    /// The surrounding neighbours of `pos` within the grid, including diagonals, clockwise
+    /// starting upwards.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |dir| self.offset(pos, dir))
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Point::new(i % width, i / width))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The first position, in row-major order, of a cell matching `predicate`.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find_map(|(pos, cell)| predicate(cell).then_some(pos))
+    }
+
+    /// All positions of cells matching `predicate`, in row-major order.
+    pub fn find_all<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Pos> + 'a {
+        self.iter()
+            .filter_map(move |(pos, cell)| predicate(cell).then_some(pos))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// # Panics
+    /// Panics if `x` is outside of the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} is outside of the {}x{} grid.",
+            self.width,
+            self.height
+        );
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The diagonals running down and to the right, starting from the bottom-left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.height)
+            .rev()
+            .map(|y| Point::new(0, y))
+            .chain((1..self.width).map(|x| Point::new(x, 0)));
+        starts.map(|start| self.ray(start, Dir8::DownRight))
+    }
+
+    /// The diagonals running down and to the left, starting from the top-left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.width)
+            .map(|x| Point::new(x, 0))
+            .chain((1..self.height).map(|y| Point::new(self.width - 1, y)));
+        starts.map(|start| self.ray(start, Dir8::DownLeft))
+    }
+
+    /// The cells from `start` in steps of `direction` until the edge of the grid.
+    pub fn ray(&self, start: Pos, direction: impl Into<Vec2<isize>>) -> impl Iterator<Item = &T> {
+        let direction = direction.into();
+        std::iter::successors(self.contains(start).then_some(start), move |&pos| {
+            self.offset(pos, direction)
+        })
+        .map(|pos| &self[pos])
+    }
+
+    /// Creates a grid of the same size with every cell mapped by `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::from_vec(self.width, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self::from_vec(width, vec![fill; width * height])
+    }
+
+    /// Mirrors the grid along its main diagonal, so that rows become columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        let cells = self.columns().flatten().cloned().collect();
+        Self::from_vec(self.height, cells)
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Self {
+        let cells = (0..self.width)
+            .flat_map(|x| (0..self.height).rev().map(move |y| &self[Point::new(x, y)]))
+            .cloned()
+            .collect();
+        Self::from_vec(self.height, cells)
+    }
+
+    /// Rotates the grid by 90 degrees counter-clockwise.
+    #[must_use]
+    pub fn rotate_counter_clockwise(&self) -> Self {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x))
+            .cloned()
+            .collect();
+        Self::from_vec(self.height, cells)
+    }
+}
+
+impl<T, P: GridIndex + Debug> Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: P) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "position {pos:?} is outside of the {}x{} grid.",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T, P: GridIndex + Debug> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, pos: P) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is outside of the {width}x{height} grid."))
+    }
+}
+
+impl FromStr for Grid<u8> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, |b| b)
+    }
+}
+
+/// Renders one row per line, without a trailing newline.
+impl<T: Copy + Into<char>> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for &cell in row {
+                write!(f, "{}", cell.into())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Error for GridError {}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line} is {found} cells wide, expected {expected} like the first line."
+            ),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, GridError};
+    use crate::point::Point;
+
+    fn sample() -> Grid<u8> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        let grid = sample();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 1)], b'e');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get(Point::new(-1i16, 0)), None);
+        assert_eq!(grid[Point::new(2i16, 0)], b'c');
+        assert_eq!(grid.to_string(), "abc\ndef");
+        assert_eq!(
+            Grid::parse_with("12\n34", |b| b - b'0').unwrap().row(1),
+            &[3, 4]
+        );
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        assert_eq!(
+            "abc\nde".parse::<Grid<u8>>(),
+            Err(GridError::Ragged {
+                line: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn finds_neighbours_within_bounds() {
+        let grid = sample();
+        assert_eq!(
+            grid.neighbours4(Point::new(0, 0)).collect::<Vec<_>>(),
+            vec![Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours8(Point::new(1, 0)).collect::<Vec<_>>(),
+            [(2, 0), (2, 1), (1, 1), (0, 1), (0, 0)].map(Point::from)
+        );
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = sample();
+        let collect = |line: Vec<&u8>| String::from_utf8(line.into_iter().copied().collect());
+        let columns: Vec<_> = grid
+            .columns()
+            .map(|c| collect(c.collect()).unwrap())
+            .collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+        let diagonals: Vec<_> = grid
+            .diagonals()
+            .map(|d| collect(d.collect()).unwrap())
+            .collect();
+        assert_eq!(diagonals, ["d", "ae", "bf", "c"]);
+        let anti: Vec<_> = grid
+            .anti_diagonals()
+            .map(|d| collect(d.collect()).unwrap())
+            .collect();
+        assert_eq!(anti, ["a", "bd", "ce", "f"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside of the 3x3 grid.")]
+    fn rejects_columns_outside() {
+        let grid: Grid<u8> = "abc\ndef\nghi".parse().unwrap();
+        grid.column(3).for_each(drop);
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid = sample();
+        assert_eq!(grid.find(|&c| c == b'e'), Some(Point::new(1, 1)));
+        assert_eq!(grid.find(|&c| c == b'z'), None);
+        assert_eq!(
+            grid.find_all(|c| b"aef".contains(c)).collect::<Vec<_>>(),
+            [(0, 0), (1, 1), (2, 1)].map(Point::from)
+        );
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = sample();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counter_clockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_counter_clockwise(), grid);
+    }
+}
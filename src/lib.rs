@@ -1,3 +1,12 @@
+pub mod cycle;
+pub mod grid;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod point;
+pub mod search;
 pub mod template;
+pub mod trace;
+pub mod viz;
 
 // Use this file to add helper functions and additional modules.
@@ -8,7 +8,7 @@ use std::process;
 
 mod args {
     use advent_of_code::template::Day;
-    use std::process;
+    use std::{path::PathBuf, process};
 
     pub enum AppArguments {
         Download {
@@ -26,6 +26,10 @@ mod args {
             day: Day,
             release: bool,
             dhat: bool,
+            profile: bool,
+            visualize: bool,
+            fps: Option<u32>,
+            verbose: bool,
             submit: Option<u8>,
         },
         All {
@@ -35,6 +39,8 @@ mod args {
             all: bool,
             day: Option<Day>,
             store: bool,
+            compare: bool,
+            report: Option<PathBuf>,
         },
         #[cfg(feature = "today")]
         Today,
@@ -50,11 +56,15 @@ mod args {
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let report = args.opt_value_from_str("--report")?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    compare,
+                    report,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -73,6 +83,10 @@ mod args {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                profile: args.contains("--profile"),
+                visualize: args.contains("--visualize"),
+                fps: args.opt_value_from_str("--fps")?,
+                verbose: args.contains("--verbose"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -103,7 +117,13 @@ fn main() {
         }
         Ok(args) => match args {
             AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                compare,
+                report,
+            } => time::handle(day, all, store, compare, report.as_deref()),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
@@ -120,8 +140,12 @@ fn main() {
                 day,
                 release,
                 dhat,
+                profile,
+                visualize,
+                fps,
+                verbose,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+            } => solve::handle(day, release, dhat, profile, visualize, fps, verbose, submit),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
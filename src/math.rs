@@ -0,0 +1,279 @@
+/// Digit manipulation and number theory helpers.
+///
+/// Digit helpers are methods of [`Digits`], implemented for all unsigned integers, and treat `0`
+/// as a number with a single digit. Modular arithmetic works on `i64`, with intermediate results
+/// widened so that products do not overflow.
+use std::ops::{Div, Mul, Rem};
+
+/// Decimal digit helpers for unsigned integers.
+pub trait Digits: Sized + Copy {
+    /// The number of decimal digits, where `0` has one digit.
+    fn num_digits(self) -> u32;
+
+    /// Appends the digits of `right`, e.g. `12.concat(345) == 12345`.
+    ///
+    /// # Panics
+    /// Panics on overflow in debug builds, like other arithmetic.
+    #[must_use]
+    fn concat(self, right: Self) -> Self;
+
+    /// Removes the digits of `right` from the end, the inverse of [`Digits::concat`], e.g.
+    /// `12345.unconcat(345) == Some(12)`. Returns [`None`] if the number does not end in `right`
+    /// or has no digits left.
+    fn unconcat(self, right: Self) -> Option<Self>;
+
+    /// Splits off the last `n` digits, e.g. `123456.split_digits(2) == (1234, 56)`.
+    fn split_digits(self, n: u32) -> (Self, Self);
+
+    /// The digits from the most to the least significant one.
+    fn digits(self) -> impl DoubleEndedIterator<Item = u8>;
+}
+
+macro_rules! impl_digits {
+    ($($t:ty),*) => {
+        $(
+            impl Digits for $t {
+                fn num_digits(self) -> u32 {
+                    self.checked_ilog10().unwrap_or(0) + 1
+                }
+
+                fn concat(self, right: Self) -> Self {
+                    self * (10 as $t).pow(right.num_digits()) + right
+                }
+
+                fn unconcat(self, right: Self) -> Option<Self> {
+                    let factor = (10 as $t).checked_pow(right.num_digits())?;
+                    (self > right && self % factor == right).then(|| self / factor)
+                }
+
+                fn split_digits(self, n: u32) -> (Self, Self) {
+                    match (10 as $t).checked_pow(n) {
+                        Some(factor) => (self / factor, self % factor),
+                        None => (0, self),
+                    }
+                }
+
+                #[allow(clippy::cast_possible_truncation)]
+                fn digits(self) -> impl DoubleEndedIterator<Item = u8> {
+                    (0..self.num_digits())
+                        .rev()
+                        .map(move |i| (self / (10 as $t).pow(i) % 10) as u8)
+                }
+            }
+        )*
+    };
+}
+
+impl_digits!(u8, u16, u32, u64, u128, usize);
+
+/* -------------------------------------------------------------------------- */
+
+/// Integers that [`gcd`] and [`lcm`] work on.
+pub trait Integer:
+    Copy + PartialEq + Default + Rem<Output = Self> + Div<Output = Self> + Mul<Output = Self>
+{
+    fn abs_value(self) -> Self;
+}
+
+macro_rules! impl_integer {
+    (unsigned: $($u:ty),*; signed: $($s:ty),*) => {
+        $(
+            impl Integer for $u {
+                fn abs_value(self) -> Self {
+                    self
+                }
+            }
+        )*
+        $(
+            impl Integer for $s {
+                fn abs_value(self) -> Self {
+                    self.abs()
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(unsigned: u8, u16, u32, u64, u128, usize; signed: i8, i16, i32, i64, i128, isize);
+
+/// The greatest common divisor, which is never negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs_value(), b.abs_value());
+    while b != T::default() {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, which is never negative. Is 0 if either number is 0.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::default() || b == T::default() {
+        return T::default();
+    }
+    (a / gcd(a, b) * b).abs_value()
+}
+
+/// The extended Euclidean algorithm: returns `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`.
+pub fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The inverse of `a` modulo `m`, in `0..m`. Returns [`None`] if `a` and `m` are not coprime.
+pub fn modinv(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = egcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// `base` to the power of `exp` modulo `m`, by repeated squaring.
+#[allow(clippy::cast_possible_truncation)]
+pub fn modpow(base: i64, mut exp: u64, m: i64) -> i64 {
+    let m = i128::from(m);
+    let mut base = i128::from(base).rem_euclid(m);
+    let mut result = 1 % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+
+    result as i64
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the Chinese remainder
+/// theorem. Moduli do not need to be coprime.
+///
+/// Returns `(x, m)`, where `x` in `0..m` is the smallest solution and `m` the least common
+/// multiple of the moduli, or [`None`] if the congruences contradict each other.
+#[allow(clippy::cast_possible_truncation)]
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(x, m), (residue, modulus)| {
+            let (g, p, _) = egcd(m, modulus);
+            let diff = residue - x;
+            if diff % g != 0 {
+                return None;
+            }
+
+            let lcm = i128::from(m / g) * i128::from(modulus);
+            let step = i128::from(diff / g) * i128::from(p) % i128::from(modulus / g);
+            let x = (i128::from(x) + i128::from(m) * step).rem_euclid(lcm);
+            Some((x as i64, i64::try_from(lcm).ok()?))
+        })
+}
+
+/// Solves the linear system `a * x + b * y = e`, `c * x + d * y = f`, given as
+/// `[[a, b], [c, d]]` and `[e, f]`, for integers `x` and `y`.
+///
+/// Returns [`None`] if there is no unique solution, or if it is not integral.
+pub fn solve_2x2([[a, b], [c, d]]: [[i64; 2]; 2], [e, f]: [i64; 2]) -> Option<(i64, i64)> {
+    let det = a * d - b * c;
+    if det == 0 {
+        return None;
+    }
+
+    let x = e * d - b * f;
+    let y = a * f - e * c;
+    (x % det == 0 && y % det == 0).then(|| (x / det, y / det))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{crt, egcd, gcd, lcm, modinv, modpow, solve_2x2, Digits};
+
+    #[test]
+    fn counts_digits() {
+        assert_eq!(0u64.num_digits(), 1);
+        assert_eq!(9u8.num_digits(), 1);
+        assert_eq!(10u32.num_digits(), 2);
+        assert_eq!(u64::MAX.num_digits(), 20);
+    }
+
+    #[test]
+    fn concatenates() {
+        assert_eq!(12u64.concat(345), 12345);
+        assert_eq!(12u64.concat(0), 120);
+        assert_eq!(0u64.concat(7), 7);
+        assert_eq!(12345u64.unconcat(345), Some(12));
+        assert_eq!(12345u64.unconcat(45), Some(123));
+        assert_eq!(12345u64.unconcat(44), None);
+        assert_eq!(345u64.unconcat(345), None);
+        assert_eq!(120u64.unconcat(0), Some(12));
+    }
+
+    #[test]
+    fn splits_digits() {
+        assert_eq!(123_456u64.split_digits(3), (123, 456));
+        assert_eq!(1000u32.split_digits(2), (10, 0));
+        assert_eq!(7u8.split_digits(5), (0, 7));
+        assert_eq!(9041u16.digits().collect::<Vec<_>>(), [9, 0, 4, 1]);
+        assert_eq!(0usize.digits().collect::<Vec<_>>(), [0]);
+        assert_eq!(120u64.digits().rev().collect::<Vec<_>>(), [0, 2, 1]);
+    }
+
+    #[test]
+    fn finds_divisors_and_multiples() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(lcm(4u64, 6), 12);
+        assert_eq!(lcm(-4i32, 6), 12);
+        assert_eq!(lcm(0u64, 6), 0);
+
+        let (g, x, y) = egcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn does_modular_arithmetic() {
+        assert_eq!(modinv(3, 11), Some(4));
+        assert_eq!(modinv(-3, 11), Some(7));
+        assert_eq!(modinv(6, 9), None);
+        assert_eq!(modpow(4, 13, 497), 445);
+        assert_eq!(modpow(-2, 3, 5), 2);
+        assert_eq!(modpow(5, 0, 1), 0);
+        assert_eq!(modpow(3, 1 << 40, i64::MAX), {
+            (0..40).fold(3i128, |x, _| x * x % i128::from(i64::MAX)) as i64
+        });
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+
+    #[test]
+    fn solves_linear_systems() {
+        // 2024 day 13, first claw machine of the example.
+        assert_eq!(
+            solve_2x2([[94, 22], [34, 67]], [8400, 5400]),
+            Some((80, 40))
+        );
+        assert_eq!(solve_2x2([[26, 67], [66, 21]], [12748, 12176]), None);
+        assert_eq!(solve_2x2([[1, 2], [2, 4]], [3, 6]), None);
+    }
+}
@@ -0,0 +1,174 @@
+/// Memoisation with an explicit cache, created for each run of a part.
+///
+/// Unlike a global cache, nothing leaks between benchmark iterations, tests or parts. Recursive
+/// functions take the [`Memo`] as an argument and compute missing values through
+/// [`Memo::get_or_insert_with`], which hands the memo back for the recursive calls:
+///
+/// ```ignore
+/// fn stones(memo: &mut Memo<(u64, u8), u64>, stone: u64, blinks: u8) -> u64 {
+///     memo.get_or_insert_with((stone, blinks), |memo| match blinks {
+///         0 => 1,
+///         _ => stones(memo, stone * 2024, blinks - 1),
+///     })
+/// }
+/// ```
+///
+/// Set `AOC_MEMO_STATS` to print the hit and miss counts of every memo when it is dropped. Memos
+/// dropped while the runner benches a part print nothing.
+use std::{
+    env,
+    fmt::{self, Display},
+    hash::Hash,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
+
+use rustc_hash::FxHashMap;
+
+/// A cache of computed values, see the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: FxHashMap<K, V>,
+    stats: MemoStats,
+}
+
+/// How often a [`Memo`] found a value in its cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self {
+            cache: FxHashMap::default(),
+            stats: MemoStats::default(),
+        }
+    }
+
+    /// The cached value for `key`, or the result of `compute`, which is then cached.
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+
+        self.stats.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// The number of cached values.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Drop for Memo<K, V> {
+    fn drop(&mut self) {
+        if print_stats() {
+            eprintln!("memo: {}", self.stats);
+        }
+    }
+}
+
+static PRINTING: AtomicBool = AtomicBool::new(true);
+
+fn print_stats() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| env::var_os("AOC_MEMO_STATS").is_some())
+        && PRINTING.load(Ordering::Relaxed)
+}
+
+/// Stops memos from printing their stats until the returned guard is dropped, e.g. while the
+/// runner benches a part.
+pub(crate) fn pause() -> Pause {
+    Pause(PRINTING.swap(false, Ordering::Relaxed))
+}
+
+pub(crate) struct Pause(bool);
+
+impl Drop for Pause {
+    fn drop(&mut self) {
+        PRINTING.store(self.0, Ordering::Relaxed);
+    }
+}
+
+impl Display for MemoStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lookups = self.hits + self.misses;
+        #[allow(clippy::cast_precision_loss)]
+        let rate = if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64 * 100.0
+        };
+        write!(
+            f,
+            "{} hits, {} misses ({rate:.1}% hit rate)",
+            self.hits, self.misses
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Memo, MemoStats};
+
+    fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        memo.get_or_insert_with(n, |memo| match n {
+            0 | 1 => n,
+            _ => fibonacci(memo, n - 1) + fibonacci(memo, n - 2),
+        })
+    }
+
+    #[test]
+    fn memoises_recursion() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(&mut memo, 90), 2_880_067_194_370_816_120);
+        assert_eq!(memo.len(), 91);
+        assert_eq!(
+            memo.stats(),
+            MemoStats {
+                hits: 88,
+                misses: 91
+            }
+        );
+
+        fibonacci(&mut memo, 90);
+        assert_eq!(memo.stats().hits, 89);
+        assert_eq!(
+            memo.stats().to_string(),
+            "89 hits, 91 misses (49.4% hit rate)"
+        );
+    }
+
+    #[test]
+    fn starts_empty() {
+        let mut memo = Memo::<u8, u8>::new();
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats().to_string(), "0 hits, 0 misses (0.0% hit rate)");
+        assert_eq!(memo.get_or_insert_with(1, |_| 2), 2);
+        assert_eq!(memo.get_or_insert_with(1, |_| 3), 2);
+    }
+}
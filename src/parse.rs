@@ -0,0 +1,374 @@
+/// Ready-made `winnow` parsers for common puzzle input shapes.
+///
+/// All parsers work on both `&str` and `&[u8]` input, see [`Text`]. Line based parsers do not
+/// consume the newline ending their line, so that they compose with [`lines`] and [`blocks`].
+///
+/// [`parse()`] runs a parser on a whole puzzle input and reports failures as a [`ParseError`],
+/// which points at the offending line and column:
+///
+/// ```ignore
+/// use winnow::combinator::separated_pair;
+///
+/// let (rules, updates): (Vec<(u16, u16)>, Vec<Vec<u16>>) = parse(
+///     input.as_bytes(),
+///     separated_pair(lines(rule), blank_line, lines(uint_list(','))),
+/// )?;
+/// ```
+use std::fmt::{self, Display};
+
+use winnow::{
+    ascii::{dec_int, dec_uint, space0, space1, Int, Uint},
+    combinator::{alt, cut_err, eof, not, opt, peek, separated, separated_pair, terminated},
+    error::{ContextError, StrContext, StrContextValue},
+    stream::{AsBStr, AsChar, Compare, Stream, StreamIsPartial},
+    token::{any, take_till, take_while},
+    PResult, Parser,
+};
+
+use crate::grid::Grid;
+
+/// Input the parsers of this module accept, i.e. `&str` and `&[u8]`.
+pub trait Text:
+    Stream<Token: AsChar + Clone, Slice: AsBStr>
+    + StreamIsPartial
+    + Compare<char>
+    + Compare<&'static str>
+{
+}
+
+impl<I> Text for I where
+    I: Stream<Token: AsChar + Clone, Slice: AsBStr>
+        + StreamIsPartial
+        + Compare<char>
+        + Compare<&'static str>
+{
+}
+
+/// A parse failure with its position in the input, see [`parse()`].
+///
+/// Displays as the position and what was expected there, followed by the offending line with
+/// the column underlined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    source_line: String,
+    expected: Vec<String>,
+    found: String,
+}
+
+impl ParseError {
+    fn new(input: &[u8], offset: usize, error: &ContextError) -> Self {
+        let (before, after) = input.split_at(offset);
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        let line_end = after
+            .iter()
+            .position(|&b| b == b'\n')
+            .unwrap_or(after.len());
+
+        let rest = String::from_utf8_lossy(&after[..line_end]);
+        let found = match rest.chars().next() {
+            Some(c) => format!("`{c}`"),
+            None if after.is_empty() => "end of input".to_string(),
+            None => "end of line".to_string(),
+        };
+
+        let expected = error
+            .context()
+            .filter_map(|context| match context {
+                StrContext::Expected(value) => Some(value.to_string()),
+                _ => None,
+            })
+            .collect();
+
+        Self {
+            line: before.iter().filter(|&&b| b == b'\n').count() + 1,
+            column: String::from_utf8_lossy(&before[line_start..])
+                .chars()
+                .count()
+                + 1,
+            source_line: String::from_utf8_lossy(&input[line_start..offset + line_end])
+                .into_owned(),
+            expected,
+            found,
+        }
+    }
+
+    /// The line of the failure, starting at 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column of the failure in characters, starting at 1.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        if self.expected.is_empty() {
+            write!(f, "unexpected {}", self.found)?;
+        } else {
+            write!(
+                f,
+                "expected {}, found {}",
+                self.expected.join(" or "),
+                self.found
+            )?;
+        }
+
+        let gutter = self.line.to_string();
+        let padding = " ".repeat(gutter.len());
+        write!(f, "\n{gutter} | {}", self.source_line)?;
+        write!(f, "\n{padding} | {}^", " ".repeat(self.column - 1))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Runs `parser` on the whole `input`, allowing trailing newlines, and locates any failure.
+pub fn parse<I, O>(input: I, parser: impl Parser<I, O, ContextError>) -> Result<O, ParseError>
+where
+    I: Text + AsBStr + Copy,
+{
+    terminated(parser, take_while(0.., '\n'))
+        .parse(input)
+        .map_err(|error| ParseError::new(input.as_bstr(), error.offset(), error.inner()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// At least one `item`, separated by `separator`. Once a separator is consumed, an item must
+/// follow, so that failures are reported at the bad item rather than after the list.
+fn list<I: Text, O, S>(
+    mut item: impl Parser<I, O, ContextError>,
+    mut separator: impl Parser<I, S, ContextError>,
+) -> impl Parser<I, Vec<O>, ContextError> {
+    move |input: &mut I| {
+        let mut values = Vec::new();
+        values.push(item.parse_next(input)?);
+        while opt(separator.by_ref()).parse_next(input)?.is_some() {
+            values.push(cut_err(item.by_ref()).parse_next(input)?);
+        }
+        Ok(values)
+    }
+}
+
+fn expected<I: Text, O>(
+    parser: impl Parser<I, O, ContextError>,
+    description: &'static str,
+) -> impl Parser<I, O, ContextError> {
+    parser.context(StrContext::Expected(StrContextValue::Description(
+        description,
+    )))
+}
+
+fn literal<I: Text>(c: char) -> impl Parser<I, char, ContextError> {
+    c.context(StrContext::Expected(StrContextValue::CharLiteral(c)))
+}
+
+/// One `line` per line, e.g. `lines(uints)`. Each line up to the next blank line, or the end
+/// of the input, must match `line` completely.
+pub fn lines<I: Text, O>(
+    line: impl Parser<I, O, ContextError>,
+) -> impl Parser<I, Vec<O>, ContextError> {
+    let end_of_line = cut_err(expected(
+        peek(alt(("\n".void(), eof.void()))),
+        "end of line",
+    ));
+    let next_line = terminated('\n', not(alt(("\n".void(), eof.void()))));
+    list(terminated(line, end_of_line), next_line)
+}
+
+/// The empty line separating two blocks of lines.
+pub fn blank_line<I: Text>(input: &mut I) -> PResult<()> {
+    expected("\n\n", "blank line").void().parse_next(input)
+}
+
+/// Blocks of lines separated by a blank line, e.g. `blocks(grid)`.
+pub fn blocks<I: Text, O>(
+    block: impl Parser<I, O, ContextError>,
+) -> impl Parser<I, Vec<O>, ContextError> {
+    separated(1.., block, blank_line)
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn uint<I: Text, T: Uint>(input: &mut I) -> PResult<T> {
+    expected(dec_uint, "unsigned integer").parse_next(input)
+}
+
+/// Unsigned integers separated by spaces, e.g. `7 6 4 2 1`.
+pub fn uints<I: Text, T: Uint>(input: &mut I) -> PResult<Vec<T>> {
+    list(uint, space1).parse_next(input)
+}
+
+/// Signed integers separated by spaces, e.g. `-3 0 12`.
+pub fn ints<I: Text, T: Int>(input: &mut I) -> PResult<Vec<T>> {
+    list(expected(dec_int::<_, T, _>, "integer"), space1).parse_next(input)
+}
+
+/// Unsigned integers separated by `separator`, e.g. `75,47,61` for `uint_list(',')`.
+pub fn uint_list<I: Text, T: Uint>(separator: char) -> impl Parser<I, Vec<T>, ContextError> {
+    list(uint, literal(separator))
+}
+
+/// A pair of unsigned integers in two columns, e.g. `3   4`.
+pub fn column_pair<I: Text, T: Uint>(input: &mut I) -> PResult<(T, T)> {
+    separated_pair(uint, expected(space1, "spaces"), uint).parse_next(input)
+}
+
+/// An ordering rule of two unsigned integers, e.g. `47|53`.
+pub fn rule<I: Text, T: Uint>(input: &mut I) -> PResult<(T, T)> {
+    separated_pair(uint, literal('|'), uint).parse_next(input)
+}
+
+/// A target value and its operands, e.g. `3267: 81 40 27`.
+pub fn equation<I: Text, T: Uint>(input: &mut I) -> PResult<(T, Vec<T>)> {
+    separated_pair(uint, (literal(':'), space0), uints).parse_next(input)
+}
+
+/// Every integer within a line, skipping anything else, e.g. `[94, 34]` for
+/// `Button A: X+94, Y+34`. A `-` directly in front of a number makes it negative.
+pub fn all_ints<I: Text, T: Int>(input: &mut I) -> PResult<Vec<T>> {
+    let mut values = Vec::new();
+
+    loop {
+        take_till(0.., |c: I::Token| {
+            let c = c.as_char();
+            c.is_ascii_digit() || c == '-' || c == '\n'
+        })
+        .void()
+        .parse_next(input)?;
+
+        if let Some(value) = opt(dec_int::<_, T, _>).parse_next(input)? {
+            values.push(value);
+            continue;
+        }
+
+        match opt(peek(any)).parse_next(input)? {
+            Some(c) if c.clone().as_char() != '\n' => {
+                // A `-` that does not start a number.
+                any.void().parse_next(input)?;
+            }
+            _ => return Ok(values),
+        }
+    }
+}
+
+/// A rectangular grid of characters, one row per line.
+pub fn grid<I: Text>(input: &mut I) -> PResult<Grid<u8>> {
+    let same_width = |rows: &Vec<I::Slice>| {
+        rows.iter()
+            .all(|row| row.as_bstr().len() == rows[0].as_bstr().len())
+    };
+
+    cut_err(lines(take_till(1.., '\n')).verify(same_width))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "rows of equal width",
+        )))
+        .map(|rows| {
+            let width = rows[0].as_bstr().len();
+            let cells = rows.iter().flat_map(|row| row.as_bstr().iter().copied());
+            Grid::from_vec(width, cells.collect())
+        })
+        .parse_next(input)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use winnow::{combinator::separated_pair, Parser};
+
+    use super::{
+        all_ints, blank_line, blocks, column_pair, equation, grid, ints, lines, parse, rule,
+        uint_list, uints,
+    };
+
+    #[test]
+    fn parses_lines_of_numbers() {
+        let mut input = "7 6 4\n1 2\n";
+        let rows: Vec<Vec<u8>> = lines(uints).parse_next(&mut input).unwrap();
+        assert_eq!(rows, [vec![7, 6, 4], vec![1, 2]]);
+        assert_eq!(input, "\n");
+
+        let mut input = &b"-3 0 12"[..];
+        assert_eq!(ints::<_, i32>(&mut input), Ok(vec![-3, 0, 12]));
+
+        let mut input = "3   4\n4   3";
+        let pairs: Vec<(u32, u32)> = lines(column_pair).parse_next(&mut input).unwrap();
+        assert_eq!(pairs, [(3, 4), (4, 3)]);
+    }
+
+    #[test]
+    fn parses_blocks() {
+        let mut input = &b"47|53\n97|13\n\n75,47\n97,61,53\n"[..];
+        let (rules, updates): (Vec<(u16, u16)>, Vec<Vec<u16>>) =
+            separated_pair(lines(rule), blank_line, lines(uint_list(',')))
+                .parse_next(&mut input)
+                .unwrap();
+        assert_eq!(rules, [(47, 53), (97, 13)]);
+        assert_eq!(updates, [vec![75, 47], vec![97, 61, 53]]);
+
+        let mut input = "#.\n.#\n\n..\n##\n";
+        let grids = blocks(grid).parse_next(&mut input).unwrap();
+        assert_eq!(grids.len(), 2);
+        assert_eq!(grids[1].to_string(), "..\n##");
+    }
+
+    #[test]
+    fn parses_equations() {
+        let mut input = "3267: 81 40 27";
+        assert_eq!(equation::<_, u64>(&mut input), Ok((3267, vec![81, 40, 27])));
+    }
+
+    #[test]
+    fn extracts_integers() {
+        let mut input = "Button A: X+94, Y-34\nPrize: X=8400";
+        assert_eq!(all_ints::<_, i64>(&mut input), Ok(vec![94, -34]));
+        assert_eq!(input, "\nPrize: X=8400");
+
+        let mut input = &b"p=0,4 v=3,-3 a-b -"[..];
+        assert_eq!(all_ints::<_, i8>(&mut input), Ok(vec![0, 4, 3, -3]));
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn rejects_ragged_grids() {
+        let mut input = "##\n#\n";
+        assert!(grid(&mut input).is_err());
+    }
+
+    #[test]
+    fn parses_whole_input() {
+        let rows: Vec<Vec<u8>> = parse("7 6\n1 2\n\n", lines(uints)).unwrap();
+        assert_eq!(rows, [vec![7, 6], vec![1, 2]]);
+    }
+
+    #[test]
+    fn locates_errors() {
+        let error = parse::<_, Vec<Vec<u8>>>("7 6\n1 -2\n", lines(uints)).unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 3));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected unsigned integer, found `-`\n2 | 1 -2\n  |   ^"
+        );
+
+        let error = parse::<_, Vec<(u8, u8)>>(&b"1|2\n3|4 5\n"[..], lines(rule)).unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 4));
+        assert!(error
+            .to_string()
+            .starts_with("line 2, column 4: expected end of line"));
+
+        let error = parse::<_, Vec<u8>>("1,2,", uint_list(',')).unwrap_err();
+        assert!(error
+            .to_string()
+            .ends_with("found end of input\n1 | 1,2,\n  |     ^"));
+    }
+}
@@ -0,0 +1,305 @@
+/// Points, vectors and directions on a 2D grid.
+///
+/// Follows the same orientation as [`Grid`](crate::grid::Grid): `x` grows to the right and `y`
+/// grows downwards, so [`Dir4::Up`] is `(0, -1)`.
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position, e.g. a cell of a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A displacement between two [`Point`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Add<Output = T>> Point<T> {
+    /// The distance when moving orthogonally, `|dx| + |dy|`.
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// The distance when moving diagonally as well, `max(|dx|, |dy|)`.
+    pub fn chebyshev(self, other: Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+/// Works for unsigned types as well, which have no `abs`.
+fn abs_diff<T: Copy + Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    a.max(b) - a.min(b)
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Vec2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<{}, {}>", self.x, self.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add<Vec2<T>> for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Vec2<T>) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub<Vec2<T>> for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Vec2<T>) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Vec2<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign<Vec2<T>> for Point<T> {
+    fn add_assign(&mut self, rhs: Vec2<T>) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign<Vec2<T>> for Point<T> {
+    fn sub_assign(&mut self, rhs: Vec2<T>) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vec2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the 4 orthogonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// All directions, clockwise starting upwards.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// The step taken when moving one cell in this direction.
+    pub fn delta<T: From<i8>>(self) -> Vec2<T> {
+        let (x, y) = match self {
+            Self::Up => (0, -1),
+            Self::Right => (1, 0),
+            Self::Down => (0, 1),
+            Self::Left => (-1, 0),
+        };
+        Vec2::new(T::from(x), T::from(y))
+    }
+}
+
+/// One of the 8 directions, including diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    /// All directions, clockwise starting upwards.
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// The next direction clockwise, i.e. a turn by 45 degrees.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// The next direction counter-clockwise, i.e. a turn by 45 degrees.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// The step taken when moving one cell in this direction.
+    pub fn delta<T: From<i8>>(self) -> Vec2<T> {
+        let (x, y) = match self {
+            Self::Up => (0, -1),
+            Self::UpRight => (1, -1),
+            Self::Right => (1, 0),
+            Self::DownRight => (1, 1),
+            Self::Down => (0, 1),
+            Self::DownLeft => (-1, 1),
+            Self::Left => (-1, 0),
+            Self::UpLeft => (-1, -1),
+        };
+        Vec2::new(T::from(x), T::from(y))
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Self::ALL[dir as usize * 2]
+    }
+}
+
+impl<T: From<i8>> From<Dir4> for Vec2<T> {
+    fn from(dir: Dir4) -> Self {
+        dir.delta()
+    }
+}
+
+impl<T: From<i8>> From<Dir8> for Vec2<T> {
+    fn from(dir: Dir8) -> Self {
+        dir.delta()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Dir4, Dir8, Point, Vec2};
+
+    #[test]
+    fn does_arithmetic() {
+        let a = Point::new(1i32, 2);
+        let b = Point::new(4, -2);
+        assert_eq!(b - a, Vec2::new(3, -4));
+        assert_eq!(a + (b - a) * 2, Point::new(7, -6));
+        assert_eq!(b - -(b - a), Point::new(7, -6));
+
+        let mut c = a;
+        c += Dir4::Down.delta();
+        assert_eq!(c, Point::new(1, 3));
+    }
+
+    #[test]
+    fn measures_distances() {
+        let a = Point::new(1i32, 2);
+        let b = Point::new(4, -2);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(Point::new(5usize, 0).manhattan(Point::new(2, 3)), 6);
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.opposite(), Dir4::Right);
+        assert_eq!(Dir8::UpLeft.turn_right(), Dir8::Up);
+        assert_eq!(Dir8::Up.turn_left(), Dir8::UpLeft);
+        assert_eq!(Dir8::DownRight.opposite(), Dir8::UpLeft);
+        for dir in Dir4::ALL {
+            assert_eq!(Dir8::from(dir).delta::<i8>(), dir.delta());
+            assert_eq!(dir.opposite().delta::<i8>(), -dir.delta());
+        }
+    }
+}
@@ -0,0 +1,547 @@
+/// Generic graph searches over a neighbour function.
+///
+/// Nodes can be anything hashable, e.g. a [`Point`](crate::point::Point) or a
+/// `(Point, Dir4)` state. Neighbours are supplied by a closure, so there is no need to build an
+/// explicit graph:
+///
+/// ```ignore
+/// let open = |&p: &Pos| grid.neighbours4(p).filter(|&q| grid[q] != b'#');
+/// let paths = search::bfs_to([start], open, |&p| p == end);
+/// let steps = paths.goal_cost();
+/// ```
+///
+/// All searches accept several start nodes and record every predecessor on a shortest path, so
+/// that [`Paths::path`] can reconstruct a single shortest path and
+/// [`Paths::on_shortest_paths`] all nodes lying on any of them.
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// The result of a search: the cost to reach every visited node and how it was reached.
+#[derive(Debug, Clone)]
+pub struct Paths<N, C> {
+    visited: FxHashMap<N, Visit<N, C>>,
+    goals: Vec<N>,
+}
+
+#[derive(Debug, Clone)]
+struct Visit<N, C> {
+    cost: C,
+    predecessors: Vec<N>,
+    /// Start nodes never get predecessors, even if reached again at no cost.
+    start: bool,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Paths<N, C> {
+    fn new() -> Self {
+        Self {
+            visited: FxHashMap::default(),
+            goals: Vec::new(),
+        }
+    }
+
+    /// The cost of a shortest path to `node`, or [`None`] if it was not reached.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.visited.get(node).map(|visit| visit.cost)
+    }
+
+    /// All reached nodes and the cost of their shortest paths, in no particular order.
+    pub fn costs(&self) -> impl Iterator<Item = (&N, C)> {
+        self.visited.iter().map(|(node, visit)| (node, visit.cost))
+    }
+
+    /// The number of reached nodes, including the start nodes.
+    pub fn len(&self) -> usize {
+        self.visited.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.visited.is_empty()
+    }
+
+    /// All goal nodes reached at the lowest cost. Empty if no goal was reached, or if the search
+    /// had no goal.
+    pub fn goals(&self) -> &[N] {
+        &self.goals
+    }
+
+    /// The cost of a shortest path to a goal.
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goals.first().and_then(|goal| self.cost(goal))
+    }
+
+    /// The direct predecessors of `node` on any shortest path to it.
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.visited
+            .get(node)
+            .map_or(&[], |visit| visit.predecessors.as_slice())
+    }
+
+    /// One shortest path from a start node to `node`, including both ends.
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        let mut visit = self.visited.get(node)?;
+
+        // the first predecessor of a node was always expanded before it, so following them
+        // cannot go round in circles, not even along moves that cost nothing.
+        let mut path = vec![node.clone()];
+        while !visit.start {
+            let previous = visit.predecessors.first()?;
+            visit = self.visited.get(previous)?;
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// One shortest path to the first goal, see [`Paths::path`].
+    pub fn goal_path(&self) -> Option<Vec<N>> {
+        self.path(self.goals.first()?)
+    }
+
+    /// All nodes lying on any shortest path to any of the `targets`, including the targets.
+    pub fn on_shortest_paths<'a>(&self, targets: impl IntoIterator<Item = &'a N>) -> FxHashSet<N>
+    where
+        N: 'a,
+    {
+        let mut nodes = FxHashSet::default();
+        let mut stack: Vec<N> = targets
+            .into_iter()
+            .filter(|target| self.visited.contains_key(target))
+            .cloned()
+            .collect();
+
+        while let Some(node) = stack.pop() {
+            if nodes.insert(node.clone()) {
+                stack.extend(self.predecessors(&node).iter().cloned());
+            }
+        }
+
+        nodes
+    }
+
+    /// Records reaching `node` from `from` at `cost`. Returns `true` if this is a new shortest
+    /// path to `node`, so that it needs to be (re-)visited.
+    fn reach(&mut self, node: N, from: &N, cost: C) -> bool
+    where
+        C: Ord,
+    {
+        match self.visited.get_mut(&node) {
+            Some(visit) if visit.start => false,
+            Some(visit) => match cost.cmp(&visit.cost) {
+                Ordering::Less => {
+                    visit.cost = cost;
+                    visit.predecessors.clear();
+                    visit.predecessors.push(from.clone());
+                    true
+                }
+                Ordering::Equal => {
+                    if !visit.predecessors.contains(from) {
+                        visit.predecessors.push(from.clone());
+                    }
+                    false
+                }
+                Ordering::Greater => false,
+            },
+            None => {
+                self.visited.insert(
+                    node,
+                    Visit {
+                        cost,
+                        predecessors: vec![from.clone()],
+                        start: false,
+                    },
+                );
+                true
+            }
+        }
+    }
+
+    /// Records `node` as a start node. Returns `true` if it was not yet known.
+    fn start(&mut self, node: N, cost: C) -> bool {
+        if self.visited.contains_key(&node) {
+            return false;
+        }
+        self.visited.insert(
+            node,
+            Visit {
+                cost,
+                predecessors: Vec::new(),
+                start: true,
+            },
+        );
+        true
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Breadth-first search from `starts`, visiting every reachable node. Costs are the number of
+/// steps taken.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+) -> Paths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs_to(starts, neighbours, |_| false)
+}
+
+/// Breadth-first search from `starts` that stops once the closest nodes matching `goal` were
+/// reached, see [`Paths::goals`].
+pub fn bfs_to<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Paths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new();
+    let mut queue = VecDeque::new();
+    let mut goal_cost = None;
+
+    for start in starts {
+        if paths.start(start.clone(), 0) {
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, cost)) = queue.pop_front() {
+        if goal_cost.is_some_and(|goal_cost| cost > goal_cost) {
+            break;
+        }
+        if goal(&node) {
+            goal_cost = Some(cost);
+            paths.goals.push(node);
+            continue;
+        }
+        if goal_cost.is_some() {
+            // Anything reached from here is further away than the goals found.
+            continue;
+        }
+
+        for next in neighbours(&node) {
+            if paths.reach(next.clone(), &node, cost + 1) {
+                queue.push_back((next, cost + 1));
+            }
+        }
+    }
+
+    paths
+}
+
+/// Dijkstra's algorithm from `starts`, visiting every reachable node. `neighbours` returns the
+/// neighbouring nodes alongside the non-negative cost of moving there.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(starts, neighbours, |_| C::default(), |_| false)
+}
+
+/// Dijkstra's algorithm from `starts` that stops once the cheapest nodes matching `goal` were
+/// reached, see [`Paths::goals`].
+pub fn dijkstra_to<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(starts, neighbours, |_| C::default(), goal)
+}
+
+/// A* search from `starts` to the cheapest nodes matching `goal`.
+///
+/// `heuristic` estimates the remaining cost to a goal. It must never overestimate it, and must
+/// be consistent (an estimate never drops by more than the cost of a move), for paths and
+/// predecessors to be shortest. Manhattan distance on a grid is a typical choice.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    goal: impl FnMut(&N) -> bool,
+) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(starts, neighbours, heuristic, goal)
+}
+
+/// An entry of the open set, ordered so that the lowest estimate is popped first.
+struct Open<N, C> {
+    estimate: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Open<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for Open<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Open<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Open<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+    }
+}
+
+fn best_first<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths::new();
+    let mut open = BinaryHeap::new();
+    let mut goal_cost = None;
+
+    for start in starts {
+        if paths.start(start.clone(), C::default()) {
+            open.push(Open {
+                estimate: heuristic(&start),
+                cost: C::default(),
+                node: start,
+            });
+        }
+    }
+
+    while let Some(Open {
+        estimate,
+        cost,
+        node,
+    }) = open.pop()
+    {
+        if goal_cost.is_some_and(|goal_cost| estimate > goal_cost) {
+            break;
+        }
+        if paths.cost(&node).is_some_and(|known| cost > known) {
+            // A cheaper way to this node was found after this entry was added.
+            continue;
+        }
+        if goal(&node) {
+            if !paths.goals.contains(&node) {
+                goal_cost = Some(cost);
+                paths.goals.push(node);
+            }
+            continue;
+        }
+
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            if paths.reach(next.clone(), &node, next_cost) {
+                open.push(Open {
+                    estimate: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    paths
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// All nodes reachable from `start`, including `start` itself.
+pub fn flood_fill<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> FxHashSet<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = FxHashSet::default();
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if seen.insert(node.clone()) {
+            stack.extend(neighbours(&node).into_iter().filter(|n| !seen.contains(n)));
+        }
+    }
+
+    seen
+}
+
+/// Splits `nodes` into connected components, e.g. the regions of a grid. `neighbours` should be
+/// symmetric. Components are returned in the order their first node appears in `nodes`.
+pub fn components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = FxHashSet::default();
+    let mut components = Vec::new();
+
+    for node in nodes {
+        if seen.contains(&node) {
+            continue;
+        }
+
+        let component: Vec<N> = flood_fill(node, &mut neighbours).into_iter().collect();
+        seen.extend(component.iter().cloned());
+        components.push(component);
+    }
+
+    components
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, bfs_to, components, dijkstra, dijkstra_to, flood_fill};
+    use crate::grid::{Grid, Pos};
+    use crate::point::{Dir4, Point};
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    fn maze() -> (Grid<u8>, Pos, Pos) {
+        let grid: Grid<u8> = MAZE.parse().unwrap();
+        let start = grid.find(|&c| c == b'S').unwrap();
+        let end = grid.find(|&c| c == b'E').unwrap();
+        (grid, start, end)
+    }
+
+    fn open<'a>(grid: &'a Grid<u8>) -> impl FnMut(&Pos) -> Vec<Pos> + 'a {
+        |&p| grid.neighbours4(p).filter(|&q| grid[q] != b'#').collect()
+    }
+
+    #[test]
+    fn finds_shortest_path() {
+        let (grid, start, end) = maze();
+        let paths = bfs_to([start], open(&grid), |&p| p == end);
+        assert_eq!(paths.goal_cost(), Some(15));
+
+        let path = paths.goal_path().unwrap();
+        assert_eq!(path.len(), 16);
+        assert_eq!((path[0], path[15]), (start, end));
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+    }
+
+    #[test]
+    fn finds_nodes_on_all_shortest_paths() {
+        let grid: Grid<u8> = "S..\n...\n..E".parse().unwrap();
+        let paths = bfs([Point::new(0, 0)], open(&grid));
+        assert_eq!(paths.cost(&Point::new(2, 2)), Some(4));
+        assert_eq!(paths.on_shortest_paths([&Point::new(2, 2)]).len(), 9);
+        assert_eq!(paths.on_shortest_paths([&Point::new(2, 0)]).len(), 3);
+        assert_eq!(paths.predecessors(&Point::new(1, 1)).len(), 2);
+    }
+
+    #[test]
+    fn searches_from_several_starts() {
+        let (grid, start, end) = maze();
+        let paths = bfs([start, end], open(&grid));
+        assert_eq!(paths.cost(&Point::new(5, 0)), Some(6));
+        assert_eq!(paths.path(&end), Some(vec![end]));
+        assert_eq!(paths.len(), grid.find_all(|&c| c != b'#').count());
+    }
+
+    /// Moving costs 1, turning by 90 degrees costs 1000.
+    fn turns(grid: &Grid<u8>) -> impl FnMut(&(Pos, Dir4)) -> Vec<((Pos, Dir4), u32)> + '_ {
+        |&(p, dir)| {
+            let mut next = vec![((p, dir.turn_left()), 1000), ((p, dir.turn_right()), 1000)];
+            if let Some(q) = grid.offset(p, dir).filter(|&q| grid[q] != b'#') {
+                next.push(((q, dir), 1));
+            }
+            next
+        }
+    }
+
+    #[test]
+    fn weighs_moves() {
+        let (grid, start, end) = maze();
+        let paths = dijkstra_to([(start, Dir4::Right)], turns(&grid), |&(p, _)| p == end);
+        assert_eq!(paths.goal_cost(), Some(5015));
+
+        let full = dijkstra([(start, Dir4::Right)], turns(&grid));
+        let cheapest = Dir4::ALL.map(|dir| full.cost(&(end, dir)));
+        assert_eq!(cheapest.into_iter().flatten().min(), Some(5015));
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra() {
+        let (grid, start, end) = maze();
+        let paths = astar(
+            [(start, Dir4::Right)],
+            turns(&grid),
+            |&(p, _)| p.manhattan(end) as u32,
+            |&(p, _)| p == end,
+        );
+        assert_eq!(paths.goal_cost(), Some(5015));
+
+        let tiles: std::collections::HashSet<_> = paths
+            .on_shortest_paths(paths.goals())
+            .into_iter()
+            .map(|(p, _)| p)
+            .collect();
+        assert_eq!(tiles.len(), 16);
+    }
+
+    #[test]
+    fn follows_moves_without_cost() {
+        let graph = |&n: &u8| match n {
+            0 => vec![(1, 0)],
+            1 => vec![(0, 0), (2, 1)],
+            _ => vec![],
+        };
+        let paths = dijkstra([0u8], graph);
+        assert!(paths.predecessors(&0).is_empty());
+        assert_eq!(paths.predecessors(&1), [0]);
+        assert_eq!(paths.path(&2), Some(vec![0, 1, 2]));
+        assert_eq!(paths.on_shortest_paths([&2]).len(), 3);
+    }
+
+    #[test]
+    fn fills_regions() {
+        let grid: Grid<u8> = "AAB\nABB\nCCA".parse().unwrap();
+        let same = |&p: &Pos| {
+            grid.neighbours4(p)
+                .filter(|&q| grid[q] == grid[p])
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(flood_fill(Point::new(0, 0), same).len(), 3);
+
+        let regions = components(grid.positions(), same);
+        let sizes: Vec<_> = regions.iter().map(Vec::len).collect();
+        assert_eq!(sizes, [3, 3, 2, 1]);
+    }
+}
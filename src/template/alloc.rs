@@ -0,0 +1,179 @@
+/// A global allocator that counts allocations, used to measure and assert allocation budgets.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::Display;
+use std::hint::black_box;
+
+/// Wraps the system allocator and counts allocations made by the current thread.
+///
+/// The `solution!` macro installs it as the global allocator of solution binaries in tests and
+/// with the `count-allocs` feature, unless the `dhat-heap` feature is enabled. Allocations made
+/// on other threads are not counted.
+pub struct CountingAlloc;
+
+thread_local! {
+    static COUNT: Cell<u64> = const { Cell::new(0) };
+    static BYTES: Cell<u64> = const { Cell::new(0) };
+}
+
+fn record(size: usize) {
+    let _ = COUNT.try_with(|c| c.set(c.get() + 1));
+    let _ = BYTES.try_with(|b| b.set(b.get() + size as u64));
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Allocations made while running a closure. A `realloc` counts as an allocation of its new size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub count: u64,
+    pub bytes: u64,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} allocations ({} bytes)", self.count, self.bytes)
+    }
+}
+
+fn snapshot() -> AllocStats {
+    AllocStats {
+        count: COUNT.with(Cell::get),
+        bytes: BYTES.with(Cell::get),
+    }
+}
+
+/// Whether [`CountingAlloc`] is the global allocator of the running binary.
+pub fn is_installed() -> bool {
+    let before = snapshot();
+    drop(black_box(Box::new(0u8)));
+    snapshot().count > before.count
+}
+
+/// Runs `func` and returns its result alongside the allocations it made on the current thread.
+///
+/// The stats are `None` if [`CountingAlloc`] is not the global allocator.
+pub fn count_allocs<T>(func: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !is_installed() {
+        return (func(), None);
+    }
+
+    let before = snapshot();
+    let result = func();
+    let after = snapshot();
+
+    let stats = AllocStats {
+        count: after.count - before.count,
+        bytes: after.bytes - before.bytes,
+    };
+
+    (result, Some(stats))
+}
+
+/// Asserts that a closure makes at most `max` allocations and returns its result.
+///
+/// ```ignore
+/// let result = assert_max_allocs!(0, || part_one(&input));
+/// assert_eq!(result, Some(18));
+/// ```
+#[macro_export]
+macro_rules! assert_max_allocs {
+    ($max:expr, $func:expr) => {{
+        let (result, stats) = $crate::template::alloc::count_allocs($func);
+        let stats = stats.expect(
+            "allocations are not counted, `assert_max_allocs!` requires the `CountingAlloc` \
+            global allocator that `solution!` installs without the `dhat-heap` feature.",
+        );
+        assert!(
+            stats.count <= $max,
+            "expected at most {} allocations, found {stats}",
+            $max
+        );
+        result
+    }};
+}
+
+/// Asserts that a closure allocates at most `max` bytes in total and returns its result.
+///
+/// ```ignore
+/// let result = assert_max_alloc_bytes!(1024, || part_one(&input));
+/// assert_eq!(result, Some(18));
+/// ```
+#[macro_export]
+macro_rules! assert_max_alloc_bytes {
+    ($max:expr, $func:expr) => {{
+        let (result, stats) = $crate::template::alloc::count_allocs($func);
+        let stats = stats.expect(
+            "allocations are not counted, `assert_max_alloc_bytes!` requires the `CountingAlloc` \
+            global allocator that `solution!` installs without the `dhat-heap` feature.",
+        );
+        assert!(
+            stats.bytes <= $max,
+            "expected at most {} allocated bytes, found {stats}",
+            $max
+        );
+        result
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{count_allocs, AllocStats};
+
+    #[cfg(test)]
+    #[global_allocator]
+    static ALLOC: super::CountingAlloc = super::CountingAlloc;
+
+    #[test]
+    fn counts_allocations() {
+        let (len, stats) = count_allocs(|| vec![0u8; 64].len());
+        assert_eq!(len, 64);
+        assert_eq!(
+            stats,
+            Some(AllocStats {
+                count: 1,
+                bytes: 64
+            })
+        );
+    }
+
+    #[test]
+    fn counts_nothing_for_allocation_free_code() {
+        let (_, stats) = count_allocs(|| (0..100u32).sum::<u32>());
+        assert_eq!(stats.unwrap().count, 0);
+    }
+
+    #[test]
+    fn passes_within_budget() {
+        let result = crate::assert_max_allocs!(1, || vec![1, 2, 3]);
+        assert_eq!(result, vec![1, 2, 3]);
+        crate::assert_max_alloc_bytes!(0, || 1 + 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected at most 1 allocations")]
+    fn fails_over_budget() {
+        crate::assert_max_allocs!(1, || (vec![1], vec![2]));
+    }
+}
@@ -1,5 +1,11 @@
+use std::process;
+
 use crate::template::{all_days, run_multi::run_multi};
 
 pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+    let timings = run_multi(&all_days().collect(), is_release, false);
+
+    if timings.has_failures() {
+        process::exit(1);
+    }
 }
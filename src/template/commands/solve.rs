@@ -2,7 +2,17 @@ use std::process::{Command, Stdio};
 
 use crate::template::Day;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    profile: bool,
+    visualize: bool,
+    fps: Option<u32>,
+    verbose: bool,
+    submit_part: Option<u8>,
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -12,12 +22,38 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
+    } else if profile {
+        // reuses the `dhat` profile, which is `release` with debug symbols.
+        cmd_args.extend([
+            "--profile".to_string(),
+            "dhat".to_string(),
+            "--features".to_string(),
+            "cpu-profile".to_string(),
+        ]);
     } else if release {
         cmd_args.push("--release".to_string());
     }
 
+    if visualize {
+        cmd_args.extend(["--features".to_string(), "viz".to_string()]);
+    }
+
+    if verbose {
+        if release || dhat || profile {
+            eprintln!("Note: traces are not compiled into optimized builds.");
+        }
+        cmd_args.extend(["--features".to_string(), "trace".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
+    if visualize {
+        cmd_args.push("--visualize".to_string());
+        if let Some(fps) = fps {
+            cmd_args.extend(["--fps".to_string(), fps.to_string()]);
+        }
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
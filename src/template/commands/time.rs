@@ -1,10 +1,20 @@
 use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
 
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{
+    all_days, readme_benchmarks, report, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    compare: bool,
+    report_dir: Option<&Path>,
+) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -21,14 +31,18 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, true);
+    let merged_timings = stored_timings.merge(&timings);
+
+    if compare {
+        print_comparison(&stored_timings, &timings);
+    }
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(merged_timings.clone()) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +51,70 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if let Some(dir) = report_dir {
+        match report::write(&merged_timings, dir) {
+            Ok(path) => {
+                println!("Wrote benchmark report to \"{}\".", path.display());
+            }
+            Err(e) => {
+                eprintln!("Failed to write benchmark report: {e}");
+            }
+        }
+    }
+}
+
+/// Prints the change of every newly timed day relative to its stored timing.
+fn print_comparison(stored: &Timings, new: &Timings) {
+    println!("\n{ANSI_BOLD}Compared to stored timings{ANSI_RESET}");
+
+    for timing in &new.data {
+        let Some(previous) = stored.data.iter().find(|t| t.day == timing.day) else {
+            println!("Day {}: no stored timing.", timing.day);
+            continue;
+        };
+
+        let change = if previous.total_nanos > 0.0 {
+            format!(
+                " ({:+.1}%)",
+                (timing.total_nanos / previous.total_nanos - 1.0) * 100.0
+            )
+        } else {
+            String::new()
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let (before, after) = (
+            Duration::from_nanos(previous.total_nanos as u64),
+            Duration::from_nanos(timing.total_nanos as u64),
+        );
+        println!("Day {}: {before:.1?} -> {after:.1?}{change}", timing.day);
+
+        // warnings are printed below the day they concern, so they go to stdout with it.
+        match (&previous.fingerprint, &timing.fingerprint) {
+            (Some(a), Some(b)) if !a.same_machine(b) => {
+                println!(
+                    "  {ANSI_ITALIC}warning: recorded on a different machine ({}).{ANSI_RESET}",
+                    a.differences(b).join(", ")
+                );
+            }
+            (Some(a), Some(b)) if a != b => {
+                println!(
+                    "  {ANSI_ITALIC}warning: recorded with a different build ({}).{ANSI_RESET}",
+                    a.differences(b).join(", ")
+                );
+            }
+            (None, _) => {
+                println!(
+                    "  {ANSI_ITALIC}warning: stored timing has no machine fingerprint.{ANSI_RESET}"
+                );
+            }
+            (_, None) => {
+                println!(
+                    "  {ANSI_ITALIC}warning: new timing has no machine fingerprint.{ANSI_RESET}"
+                );
+            }
+            _ => {}
+        }
+    }
 }
@@ -0,0 +1,238 @@
+/// Describes the machine and build a set of timings was recorded with.
+use std::{collections::HashMap, fmt::Display, fs, process::Command, thread};
+use tinyjson::JsonValue;
+
+/// Identifies the machine and build settings of a timing run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fingerprint {
+    pub cpu: String,
+    pub cores: usize,
+    pub kernel: String,
+    pub rustc: String,
+    pub target: String,
+    pub profile: String,
+    pub opt_level: String,
+    pub features: Vec<String>,
+}
+
+impl Fingerprint {
+    /// Fingerprints the running process. Build settings are those the crate was compiled with.
+    pub fn current() -> Self {
+        Self {
+            cpu: cpu_model().unwrap_or_else(|| "unknown".into()),
+            cores: thread::available_parallelism().map_or(0, std::num::NonZero::get),
+            kernel: kernel_version().unwrap_or_else(|| "unknown".into()),
+            rustc: env!("AOC_BUILD_RUSTC").into(),
+            target: env!("AOC_BUILD_TARGET").into(),
+            profile: env!("AOC_BUILD_PROFILE").into(),
+            opt_level: env!("AOC_BUILD_OPT_LEVEL").into(),
+            features: env!("AOC_BUILD_FEATURES")
+                .split(',')
+                .filter(|f| !f.is_empty())
+                .map(String::from)
+                .collect(),
+        }
+    }
+
+    /// Whether both fingerprints were taken on the same hardware.
+    pub fn same_machine(&self, other: &Self) -> bool {
+        self.cpu == other.cpu && self.cores == other.cores
+    }
+
+    /// Lists every field that differs between both fingerprints, e.g. `cpu: A vs B`.
+    pub fn differences(&self, other: &Self) -> Vec<String> {
+        let features = |f: &Self| format!("[{}]", f.features.join(", "));
+
+        [
+            ("cpu", self.cpu.clone(), other.cpu.clone()),
+            ("cores", self.cores.to_string(), other.cores.to_string()),
+            ("kernel", self.kernel.clone(), other.kernel.clone()),
+            ("rustc", self.rustc.clone(), other.rustc.clone()),
+            ("target", self.target.clone(), other.target.clone()),
+            ("profile", self.profile.clone(), other.profile.clone()),
+            ("opt-level", self.opt_level.clone(), other.opt_level.clone()),
+            ("features", features(self), features(other)),
+        ]
+        .into_iter()
+        .filter(|(_, a, b)| a != b)
+        .map(|(field, a, b)| format!("{field}: {a} vs {b}"))
+        .collect()
+    }
+}
+
+impl Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({} cores), {}, {}, {}, {} (opt-level {})",
+            self.cpu,
+            self.cores,
+            self.kernel,
+            self.rustc,
+            self.target,
+            self.profile,
+            self.opt_level
+        )?;
+        if !self.features.is_empty() {
+            write!(f, ", features: {}", self.features.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+fn cpu_model() -> Option<String> {
+    if let Ok(cpuinfo) = fs::read_to_string("/proc/cpuinfo") {
+        return cpuinfo
+            .lines()
+            .find(|l| l.starts_with("model name") || l.starts_with("Model"))
+            .and_then(|l| l.split_once(':'))
+            .map(|(_, model)| model.trim().to_string());
+    }
+
+    command_output("sysctl", &["-n", "machdep.cpu.brand_string"])
+}
+
+fn kernel_version() -> Option<String> {
+    command_output("uname", &["-sr"]).or_else(|| {
+        fs::read_to_string("/proc/sys/kernel/osrelease")
+            .ok()
+            .map(|v| v.trim().to_string())
+    })
+}
+
+fn command_output(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(cmd).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let output = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!output.is_empty()).then_some(output)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Fingerprint> for JsonValue {
+    fn from(value: &Fingerprint) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("cpu".into(), JsonValue::String(value.cpu.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("cores".into(), JsonValue::Number(value.cores as f64));
+        map.insert("kernel".into(), JsonValue::String(value.kernel.clone()));
+        map.insert("rustc".into(), JsonValue::String(value.rustc.clone()));
+        map.insert("target".into(), JsonValue::String(value.target.clone()));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert(
+            "opt_level".into(),
+            JsonValue::String(value.opt_level.clone()),
+        );
+        map.insert(
+            "features".into(),
+            JsonValue::Array(
+                value
+                    .features
+                    .iter()
+                    .map(|f| JsonValue::String(f.clone()))
+                    .collect(),
+            ),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Fingerprint {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected fingerprint to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected fingerprint.{key} to be a string."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let cores = json
+            .get("cores")
+            .and_then(|v| v.get::<f64>())
+            .map(|c| *c as usize)
+            .ok_or("Expected fingerprint.cores to be a number.")?;
+
+        let features = json
+            .get("features")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .and_then(|v| v.iter().map(|f| f.get::<String>().cloned()).collect())
+            .ok_or("Expected fingerprint.features to be an array of strings.")?;
+
+        Ok(Fingerprint {
+            cpu: string("cpu")?,
+            cores,
+            kernel: string("kernel")?,
+            rustc: string("rustc")?,
+            target: string("target")?,
+            profile: string("profile")?,
+            opt_level: string("opt_level")?,
+            features,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Fingerprint;
+    use tinyjson::JsonValue;
+
+    fn get_mock_fingerprint() -> Fingerprint {
+        Fingerprint {
+            cpu: "Mock CPU".into(),
+            cores: 8,
+            kernel: "Linux 6.1".into(),
+            rustc: "rustc 1.83.0".into(),
+            target: "x86_64-unknown-linux-gnu".into(),
+            profile: "release".into(),
+            opt_level: "3".into(),
+            features: vec!["dhat-heap".into()],
+        }
+    }
+
+    #[test]
+    fn fingerprints_current_build() {
+        let fingerprint = Fingerprint::current();
+        assert_eq!(fingerprint.target.is_empty(), false);
+        assert_eq!(fingerprint.rustc.starts_with("rustc"), true);
+        assert_eq!(fingerprint.features.contains(&"test-lib".into()), true);
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let fingerprint = get_mock_fingerprint();
+        let json = JsonValue::from(&fingerprint);
+        assert_eq!(Fingerprint::try_from(&json).unwrap(), fingerprint);
+    }
+
+    #[test]
+    fn lists_differences() {
+        let a = get_mock_fingerprint();
+        let mut b = get_mock_fingerprint();
+        assert_eq!(a.same_machine(&b), true);
+        assert_eq!(a.differences(&b).len(), 0);
+
+        b.cpu = "Other CPU".into();
+        b.features = vec![];
+        assert_eq!(a.same_machine(&b), false);
+        assert_eq!(
+            a.differences(&b),
+            vec![
+                "cpu: Mock CPU vs Other CPU".to_string(),
+                "features: [dhat-heap] vs []".to_string()
+            ]
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+use crate::template::Day;
+
+/// Puzzle input with normalised line endings.
+///
+/// Carriage returns preceding a newline are stripped and the text always ends in
+/// exactly one `\n` (unless it is empty), so solutions can rely on a uniform layout
+/// regardless of how the file was saved.
+///
+/// [`Input`] dereferences to [`str`], so it can be passed to any `fn(&str)` solution.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Input(String);
+
+impl Input {
+    /// Returns the normalised input as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the normalised input as a byte slice.
+    pub fn bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    /// An iterator over the lines of the input, without line terminators.
+    pub fn lines(&self) -> std::str::Lines<'_> {
+        self.0.lines()
+    }
+
+    /// The rows of the input as byte slices, without line terminators.
+    pub fn grid(&self) -> Vec<&[u8]> {
+        self.lines().map(str::as_bytes).collect()
+    }
+
+    /// An iterator over blocks of lines separated by a blank line.
+    ///
+    /// Blocks do not include a trailing newline.
+    pub fn blocks(&self) -> impl Iterator<Item = &str> {
+        self.0
+            .strip_suffix('\n')
+            .unwrap_or(&self.0)
+            .split("\n\n")
+            .filter(|block| !block.is_empty())
+    }
+
+    /// The length of the first line in bytes.
+    pub fn width(&self) -> usize {
+        self.lines().next().map_or(0, str::len)
+    }
+
+    /// The number of lines in the input.
+    pub fn height(&self) -> usize {
+        self.lines().count()
+    }
+}
+
+fn normalise(s: &str) -> String {
+    let mut normalised = s.replace("\r\n", "\n");
+    let len = normalised.trim_end_matches('\n').len();
+    normalised.truncate(len);
+    if !normalised.is_empty() {
+        normalised.push('\n');
+    }
+    normalised
+}
+
+impl From<&str> for Input {
+    fn from(value: &str) -> Self {
+        Self(normalise(value))
+    }
+}
+
+impl From<String> for Input {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl Deref for Input {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Input {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for Input {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Helper function that reads a text file to an [`Input`].
+///
+/// # Panics
+/// Panics with a descriptive message if the file cannot be loaded, see [`try_read_file`].
+#[must_use]
+pub fn read_file(folder: &str, day: Day) -> Input {
+    try_read_file(folder, day).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Helper function that reads a text file to an [`Input`], appending a part suffix. E.g. like `01-2.txt`.
+///
+/// # Panics
+/// Panics with a descriptive message if the file cannot be loaded, see [`try_read_file_part`].
+#[must_use]
+pub fn read_file_part(folder: &str, day: Day, part: u8) -> Input {
+    try_read_file_part(folder, day, part).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Reads `data/<folder>/<day>.txt` relative to the project root.
+pub fn try_read_file(folder: &str, day: Day) -> Result<Input, InputError> {
+    load(folder, day, &format!("{day}.txt"))
+}
+
+/// Reads `data/<folder>/<day>-<part>.txt` relative to the project root.
+pub fn try_read_file_part(folder: &str, day: Day, part: u8) -> Result<Input, InputError> {
+    load(folder, day, &format!("{day}-{part}.txt"))
+}
+
+/// Example files embedded at compile time, see `build.rs`.
+#[cfg(feature = "embed-inputs")]
+static EMBEDDED_EXAMPLES: &[(&str, &str)] =
+    include!(concat!(env!("OUT_DIR"), "/embedded_examples.rs"));
+
+fn load(folder: &str, day: Day, file_name: &str) -> Result<Input, InputError> {
+    #[cfg(feature = "embed-inputs")]
+    if folder == "examples" {
+        if let Some((_, contents)) = EMBEDDED_EXAMPLES.iter().find(|(f, _)| *f == file_name) {
+            return Ok(Input::from(*contents));
+        }
+    }
+
+    let root = project_root().ok_or(InputError::ProjectRootNotFound)?;
+    let path = root.join("data").join(folder).join(file_name);
+
+    let contents = fs::read_to_string(&path).map_err(|source| match source.kind() {
+        io::ErrorKind::NotFound => InputError::NotFound {
+            path: path.clone(),
+            folder: folder.into(),
+            day,
+        },
+        _ => InputError::IO {
+            path: path.clone(),
+            source,
+        },
+    })?;
+
+    if contents.trim().is_empty() {
+        return Err(InputError::Empty {
+            path,
+            folder: folder.into(),
+            day,
+        });
+    }
+
+    Ok(Input::from(contents))
+}
+
+/// Locates the project root, i.e. the directory containing `Cargo.toml`.
+///
+/// Prefers `CARGO_MANIFEST_DIR` (set by `cargo run` and `cargo test`) and otherwise walks
+/// up from the working directory, then from the location of the running executable.
+pub fn project_root() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("CARGO_MANIFEST_DIR") {
+        let dir = PathBuf::from(dir);
+        if dir.join("Cargo.toml").is_file() {
+            return Some(dir);
+        }
+    }
+
+    env::current_dir()
+        .ok()
+        .and_then(|cwd| find_root_from(&cwd))
+        .or_else(|| {
+            env::current_exe()
+                .ok()
+                .and_then(|exe| find_root_from(exe.parent()?))
+        })
+}
+
+fn find_root_from(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join("Cargo.toml").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// An error which can be returned when loading an [`Input`] from disk.
+#[derive(Debug)]
+pub enum InputError {
+    ProjectRootNotFound,
+    NotFound {
+        path: PathBuf,
+        folder: String,
+        day: Day,
+    },
+    Empty {
+        path: PathBuf,
+        folder: String,
+        day: Day,
+    },
+    IO {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::IO { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::ProjectRootNotFound => write!(
+                f,
+                "could not locate the project root. Run the solution from within the project \
+                directory or set `CARGO_MANIFEST_DIR`."
+            ),
+            InputError::NotFound { path, folder, day } => {
+                write!(f, "could not find \"{}\". ", path.display())?;
+                if folder == "inputs" {
+                    write!(f, "Try running `cargo download {day}` to fetch your input.")
+                } else {
+                    write!(f, "Try running `cargo scaffold {day}` to create it.")
+                }
+            }
+            InputError::Empty { path, folder, day } => {
+                write!(f, "\"{}\" is empty. ", path.display())?;
+                if folder == "inputs" {
+                    write!(
+                        f,
+                        "Try running `cargo download {day}` or paste your puzzle input into it."
+                    )
+                } else {
+                    write!(
+                        f,
+                        "Copy the example from the puzzle description into the scaffolded file."
+                    )
+                }
+            }
+            InputError::IO { path, source } => {
+                write!(f, "could not read \"{}\": {source}", path.display())
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_root_from, load, Input, InputError};
+    use crate::day;
+
+    #[test]
+    fn normalises_line_endings() {
+        let input = Input::from("ab\r\ncd\r\n");
+        assert_eq!(input.as_str(), "ab\ncd\n");
+    }
+
+    #[test]
+    fn normalises_trailing_newline() {
+        assert_eq!(Input::from("ab\ncd").as_str(), "ab\ncd\n");
+        assert_eq!(Input::from("ab\ncd\n\n\n").as_str(), "ab\ncd\n");
+        assert_eq!(Input::from("").as_str(), "");
+    }
+
+    #[test]
+    fn provides_grid_dimensions() {
+        let input = Input::from("abc\ndef\r\n");
+        assert_eq!(input.width(), 3);
+        assert_eq!(input.height(), 2);
+        assert_eq!(input.grid(), vec![b"abc".as_slice(), b"def".as_slice()]);
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let input = Input::from("a\nb\n\nc\r\n\r\nd");
+        let blocks: Vec<&str> = input.blocks().collect();
+        assert_eq!(blocks, vec!["a\nb", "c", "d"]);
+    }
+
+    #[test]
+    fn derefs_to_str() {
+        fn takes_str(s: &str) -> usize {
+            s.len()
+        }
+        assert_eq!(takes_str(&Input::from("ab")), 3);
+    }
+
+    #[test]
+    fn finds_project_root_from_subdirectory() {
+        let root = env!("CARGO_MANIFEST_DIR");
+        let nested = std::path::Path::new(root).join("src").join("template");
+        assert_eq!(find_root_from(&nested).unwrap(), std::path::Path::new(root));
+    }
+
+    #[test]
+    fn errors_for_missing_input() {
+        let err = load("inputs", day!(25), "does-not-exist.txt").unwrap_err();
+        assert!(matches!(err, InputError::NotFound { .. }));
+        assert!(err.to_string().contains("cargo download 25"));
+    }
+
+    #[test]
+    fn errors_for_empty_input() {
+        let err = load("inputs", day!(1), ".keep").unwrap_err();
+        assert!(matches!(err, InputError::Empty { .. }));
+        assert!(err.to_string().contains("is empty"));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -1,13 +1,20 @@
-use std::{env, fs};
-
+pub mod alloc;
 pub mod aoc_cli;
 pub mod commands;
+pub mod ocr;
+pub mod prop;
 pub mod runner;
 
 pub use day::*;
+pub use input::*;
 
 mod day;
+mod fingerprint;
+mod input;
+#[cfg(feature = "cpu-profile")]
+mod profiler;
 mod readme_benchmarks;
+mod report;
 mod run_multi;
 mod timings;
 
@@ -15,43 +22,72 @@ pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
-#[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
-
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
-#[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
-
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Alternatively, parts can list several competing implementations, the first of which is the
+/// primary one. The runner runs and benches each of them and fails if their answers disagree:
+///
+/// ```ignore
+/// advent_of_code::solution!(2, part_one: [part_one], part_two: [part_two, part_two_naive]);
+/// ```
+///
+/// A part that leaves state behind, e.g. a global cache, can name a `reset` function after its
+/// implementations, which the runner calls before every run and bench sample of the part, outside
+/// of the timer. `reset:` before the parts sets one for every part. The samples of such a part are
+/// then stored with the `reset` timing mode:
+///
+/// ```ignore
+/// advent_of_code::solution!(11, reset: clear_cache);
+/// advent_of_code::solution!(2, part_one: [part_one], part_two: [part_two] reset clear_cache);
+/// ```
+///
+/// `parse:` as the first option names the function the parts parse the input with. `cargo time`
+/// benches it on its own, which fills the `parse` column of the benchmark table:
+///
+/// ```ignore
+/// advent_of_code::solution!(5, parse: parse_manuals);
+/// ```
+///
+/// With the `embed-inputs` feature, the day's input is embedded into the binary at compile time,
+/// so the binary neither reads from disk nor depends on the working directory.
+///
+/// In tests, or with the `count-allocs` feature, [`alloc::CountingAlloc`] is installed as the
+/// global allocator, which enables [`assert_max_allocs!`](crate::assert_max_allocs). Other builds,
+/// including the benchmarks of `cargo time`, keep the system allocator.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr, parse: $parse:ident $(, $($rest:tt)*)?) => {
+        $crate::solution!(@with [$parse] $day $(, $($rest)*)?);
+    };
+
+    (@with [$($parse:ident)?] $day:expr) => {
+        $crate::solution!(@impl $day, [$($parse)?], [1, None, part_one] [2, None, part_two]);
+    };
+    (@with [$($parse:ident)?] $day:expr, 1) => {
+        $crate::solution!(@impl $day, [$($parse)?], [1, None, part_one]);
+    };
+    (@with [$($parse:ident)?] $day:expr, 2) => {
+        $crate::solution!(@impl $day, [$($parse)?], [2, None, part_two]);
+    };
+    (@with [$($parse:ident)?] $day:expr, reset: $reset:ident $(,)?) => {
+        $crate::solution!(@impl $day, [$($parse)?], [1, Some($reset), part_one] [2, Some($reset), part_two]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    (@with [$($parse:ident)?] $day:expr, reset: $reset:ident, $( $part:ident: [ $( $func:ident ),+ $(,)? ] ),+ $(,)?) => {
+        $crate::solution!(@impl $day, [$($parse)?], $( [$crate::solution!(@part $part), Some($reset), $( $func ),+] )+);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    (@with [$($parse:ident)?] $day:expr, $( $part:ident: [ $( $func:ident ),+ $(,)? ] $(reset $reset:ident)? ),+ $(,)?) => {
+        $crate::solution!(@impl $day, [$($parse)?], $( [$crate::solution!(@part $part), $crate::solution!(@reset $($reset)?), $( $func ),+] )+);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@part part_one) => { 1 };
+    (@part part_two) => { 2 };
+
+    (@reset) => { None };
+    (@reset $reset:ident) => { Some($reset) };
+
+    (@impl $day:expr, [$($parse:ident)?], $( [$part:expr, $reset:expr, $( $func:ident ),+] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +95,34 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(all(any(test, feature = "count-allocs"), not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc::CountingAlloc = $crate::template::alloc::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            #[cfg(all(feature = "embed-inputs", not(test)))]
+            let input = $crate::template::Input::from(include!(concat!(
+                env!("OUT_DIR"),
+                "/input-",
+                $day,
+                ".rs"
+            )));
+            #[cfg(not(all(feature = "embed-inputs", not(test))))]
+            let input = match $crate::template::try_read_file("inputs", DAY) {
+                Ok(input) => input,
+                Err(e) => {
+                    eprintln!("{e}");
+                    std::process::exit(1);
+                }
+            };
+            report_fingerprint();
+            $( run_parse($parse, &input); )?
+            $( run_variants(&[$( stringify!($func) ),+], &[$( $func ),+], &input, DAY, $part, $reset); )*
         }
     };
+
+    ($day:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@with [] $day $(, $($rest)*)?);
+    };
 }
@@ -0,0 +1,168 @@
+//! Recognises the block letters some puzzles draw their answer with.
+//!
+//! Supports the 6 pixel high font (e.g. 2016 day 8, 2019 days 8 and 11, 2021 day 13, 2022 day 10)
+//! and the 10 pixel high font (2018 day 10). Lit pixels are `#` or `█`, anything else is unlit.
+
+/// Glyphs of the 6 pixel high font, trimmed to their lit columns.
+#[rustfmt::skip]
+static FONT_6: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Glyphs of the 10 pixel high font, trimmed to their lit columns.
+#[rustfmt::skip]
+static FONT_10: &[(char, [&str; 10])] = &[
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+fn is_lit(c: char) -> bool {
+    matches!(c, '#' | '█')
+}
+
+/// Decodes block letters drawn with `#` or `█` into a string, e.g. `"EHZRUBPZ"`.
+///
+/// Surrounding blank rows and columns are ignored. Returns [`None`] if the drawing is not
+/// 6 or 10 pixels high, or if it contains a glyph that is not a known letter.
+pub fn decode(art: &str) -> Option<String> {
+    let rows: Vec<Vec<bool>> = art
+        .lines()
+        .map(|line| line.chars().map(is_lit).collect())
+        .skip_while(|row: &Vec<bool>| !row.contains(&true))
+        .collect();
+    let height = rows.iter().rposition(|row| row.contains(&true))? + 1;
+    let rows = &rows[..height];
+
+    let width = rows.iter().map(Vec::len).max()?;
+    let lit = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+    let column_is_blank = |x: usize| (0..height).all(|y| !lit(x, y));
+
+    let mut decoded = String::new();
+    let mut x = 0;
+    while x < width {
+        if column_is_blank(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && !column_is_blank(x) {
+            x += 1;
+        }
+
+        let glyph: Vec<String> = (0..height)
+            .map(|y| {
+                (start..x)
+                    .map(|x| if lit(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+
+        decoded.push(recognise(&glyph)?);
+    }
+
+    (!decoded.is_empty()).then_some(decoded)
+}
+
+fn recognise(glyph: &[String]) -> Option<char> {
+    let matches = |rows: &[&str]| rows.iter().eq(glyph.iter());
+    match glyph.len() {
+        6 => FONT_6
+            .iter()
+            .find(|(_, rows)| matches(rows))
+            .map(|(c, _)| *c),
+        10 => FONT_10
+            .iter()
+            .find(|(_, rows)| matches(rows))
+            .map(|(c, _)| *c),
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode, FONT_10, FONT_6};
+
+    /// Renders `text` in the 6 pixel high font, with one blank column between letters.
+    fn render_6(text: &str, lit: char, unlit: char) -> String {
+        (0..6)
+            .map(|y| {
+                text.chars()
+                    .map(|c| {
+                        let (_, rows) = FONT_6.iter().find(|(g, _)| *g == c).unwrap();
+                        format!("{}.", rows[y])
+                    })
+                    .collect::<String>()
+                    .replace('#', &lit.to_string())
+                    .replace('.', &unlit.to_string())
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn decodes_every_letter_of_small_font() {
+        let alphabet: String = FONT_6.iter().map(|(c, _)| *c).collect();
+        assert_eq!(decode(&render_6(&alphabet, '#', '.')), Some(alphabet));
+    }
+
+    #[test]
+    fn decodes_block_characters() {
+        let art = format!("\n{}\n\n", render_6("HELLO", '█', ' '));
+        assert_eq!(decode(&art), Some("HELLO".into()));
+    }
+
+    #[test]
+    fn decodes_large_font() {
+        let art: String = (0..10)
+            .map(|y| {
+                FONT_10
+                    .iter()
+                    .map(|(_, rows)| format!("{}..", rows[y]))
+                    .collect::<String>()
+                    + "\n"
+            })
+            .collect();
+        let alphabet: String = FONT_10.iter().map(|(c, _)| *c).collect();
+        assert_eq!(decode(&art), Some(alphabet));
+    }
+
+    #[test]
+    fn rejects_unknown_glyphs() {
+        assert_eq!(decode("#\n#\n#\n#\n#\n#"), None);
+        assert_eq!(decode("###\n#.#\n###"), None);
+        assert_eq!(decode("...\n..."), None);
+    }
+}
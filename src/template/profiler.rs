@@ -0,0 +1,98 @@
+/// Samples solution parts with `pprof` and writes a flamegraph and protobuf profile per part.
+use std::error::Error;
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::hint::black_box;
+use std::io::{self, stdout, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use pprof::protos::Message;
+
+use crate::template::{project_root, Day, ANSI_ITALIC, ANSI_RESET};
+
+/// Sampling frequency in Hz. Not a round number to avoid sampling in lockstep with periodic work.
+const FREQUENCY: i32 = 997;
+
+/// How long each part is run for under the profiler.
+const DURATION: Duration = Duration::from_secs(5);
+
+/// Runs `func` repeatedly under the sampling profiler and writes the results to
+/// `target/profiles/<day>-<name>/`, e.g. `06-part2`. Returns the directory the profile was written to.
+pub fn profile<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    day: Day,
+    name: &str,
+) -> Result<PathBuf, ProfileError> {
+    let dir = project_root()
+        .ok_or(ProfileError::ProjectRootNotFound)?
+        .join("target")
+        .join("profiles")
+        .join(format!("{day}-{name}"));
+
+    print!(" > {ANSI_ITALIC}profiling{ANSI_RESET}");
+    let _ = stdout().flush();
+
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()?;
+
+    let timer = Instant::now();
+    loop {
+        black_box(func(black_box(input)));
+        if timer.elapsed() > DURATION {
+            break;
+        }
+    }
+
+    let report = guard.report().build()?;
+    drop(guard);
+
+    fs::create_dir_all(&dir)?;
+    report.flamegraph(File::create(dir.join("flamegraph.svg"))?)?;
+
+    let mut content = Vec::new();
+    report
+        .pprof()?
+        .encode(&mut content)
+        .map_err(|e| ProfileError::IO(io::Error::other(e)))?;
+    fs::write(dir.join("profile.pb"), content)?;
+
+    Ok(dir)
+}
+
+/// An error which can be returned when profiling a solution part.
+#[derive(Debug)]
+pub enum ProfileError {
+    ProjectRootNotFound,
+    Profiler(pprof::Error),
+    IO(io::Error),
+}
+
+impl From<pprof::Error> for ProfileError {
+    fn from(e: pprof::Error) -> Self {
+        ProfileError::Profiler(e)
+    }
+}
+
+impl From<io::Error> for ProfileError {
+    fn from(e: io::Error) -> Self {
+        ProfileError::IO(e)
+    }
+}
+
+impl Error for ProfileError {}
+
+impl Display for ProfileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProfileError::ProjectRootNotFound => {
+                write!(f, "could not locate the project root to write profiles to.")
+            }
+            ProfileError::Profiler(e) => write!(f, "profiler failed: {e}"),
+            ProfileError::IO(e) => write!(f, "could not write profile: {e}"),
+        }
+    }
+}
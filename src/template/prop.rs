@@ -0,0 +1,361 @@
+/// Property-based testing with seeded, per-day input generators.
+///
+/// A generator builds a random puzzle input from an [`Rng`], a property checks a solution
+/// against it. [`check`] runs a property for many generated inputs and reports the first input
+/// it fails for, alongside the seed to reproduce the run with:
+///
+/// ```ignore
+/// use advent_of_code::template::prop::{self, Rng};
+///
+/// fn generate(rng: &mut Rng) -> String {
+///     rng.lines(1..=rng.size(), |rng| rng.range(0..=9u8).to_string())
+/// }
+///
+/// prop::check(generate, prop::agrees_with(part_two, part_two_naive));
+/// ```
+///
+/// Runs can be configured with the `AOC_PROP_CASES` (default: 100) and `AOC_PROP_SEED`
+/// environment variables. Runs use the same seed unless `AOC_PROP_SEED` is set, either to a
+/// number or to `random` for a seed from the clock.
+use std::any::Any;
+use std::env;
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::template::Input;
+
+const DEFAULT_CASES: usize = 100;
+
+/// Seed of runs without `AOC_PROP_SEED`, so that `cargo test` is deterministic.
+const DEFAULT_SEED: u64 = 0x5eed_a0c0;
+
+/// Largest [`Rng::size`] hint, reached by the last case of a run.
+const MAX_SIZE: usize = 100;
+
+/// A small, seedable pseudo-random number generator (`SplitMix64`).
+///
+/// Alongside randomness, it carries a size hint that grows over a run, so that early cases are
+/// small and failures tend to be found on small inputs first.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+    size: usize,
+}
+
+/// Integer types that [`Rng::range`] can generate.
+pub trait Int: Copy {
+    fn to_i128(self) -> i128;
+    fn from_i128(value: i128) -> Self;
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {
+        $(
+            impl Int for $t {
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                fn from_i128(value: i128) -> Self {
+                    value as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: seed,
+            size: MAX_SIZE,
+        }
+    }
+
+    /// Returns a uniformly distributed `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// The size hint of the current case, between 1 and 100.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns an integer in `range`.
+    ///
+    /// # Panics
+    /// Panics if `range` is empty.
+    pub fn range<T: Int>(&mut self, range: RangeInclusive<T>) -> T {
+        let (start, end) = (range.start().to_i128(), range.end().to_i128());
+        assert!(start <= end, "cannot generate a value in an empty range.");
+        #[allow(clippy::cast_sign_loss)]
+        let span = (end - start + 1) as u128;
+        let value = u128::from(self.next_u64()) % span;
+        #[allow(clippy::cast_possible_wrap)]
+        T::from_i128(start + value as i128)
+    }
+
+    pub fn bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// Returns `true` with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        #[allow(clippy::cast_precision_loss)]
+        let sample = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        sample < p
+    }
+
+    /// Picks a random element of `items`.
+    ///
+    /// # Panics
+    /// Panics if `items` is empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..=items.len() - 1)]
+    }
+
+    /// Shuffles `items` in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..=i));
+        }
+    }
+
+    /// Generates `len` elements.
+    pub fn vec<T>(
+        &mut self,
+        len: RangeInclusive<usize>,
+        mut f: impl FnMut(&mut Self) -> T,
+    ) -> Vec<T> {
+        let len = self.range(len);
+        (0..len).map(|_| f(self)).collect()
+    }
+
+    /// Generates `len` lines, each terminated by a newline.
+    pub fn lines(
+        &mut self,
+        len: RangeInclusive<usize>,
+        f: impl FnMut(&mut Self) -> String,
+    ) -> String {
+        self.vec(len, f)
+            .into_iter()
+            .map(|line| line + "\n")
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Checks `property` for inputs built by `generate`.
+///
+/// Inputs are normalised like puzzle inputs, see [`Input`]. Panics in the property count as
+/// failures.
+///
+/// # Panics
+/// Panics with the failing input and the seed of the run if the property does not hold.
+pub fn check(generate: impl Fn(&mut Rng) -> String, property: impl Fn(&str) -> Result<(), String>) {
+    let cases = env::var("AOC_PROP_CASES")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .unwrap_or(DEFAULT_CASES);
+
+    let seed = parse_seed(env::var("AOC_PROP_SEED").ok().as_deref());
+
+    let mut rng = Rng::new(seed);
+
+    for case in 0..cases {
+        rng.size = 1 + case * (MAX_SIZE - 1) / cases.saturating_sub(1).max(1);
+        let input = Input::from(generate(&mut rng));
+
+        let result = catch_unwind(AssertUnwindSafe(|| property(&input)))
+            .unwrap_or_else(|panic| Err(format!("panicked: {}", panic_message(&panic))));
+
+        if let Err(reason) = result {
+            panic!(
+                "property failed for case {} of {cases}: {reason}\n\n\
+                input:\n{input}\n\
+                reproduce with AOC_PROP_SEED={seed}",
+                case + 1
+            );
+        }
+    }
+}
+
+/// The seed set by `AOC_PROP_SEED`: a number, `random` or unset for [`DEFAULT_SEED`].
+fn parse_seed(seed: Option<&str>) -> u64 {
+    match seed.map(str::trim) {
+        None | Some("") => DEFAULT_SEED,
+        Some("random") =>
+        {
+            #[allow(clippy::cast_possible_truncation)]
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_nanos() as u64)
+        }
+        Some(seed) => seed.parse().unwrap_or_else(|_| {
+            panic!("invalid AOC_PROP_SEED `{seed}`, expecting a number or `random`.")
+        }),
+    }
+}
+
+fn panic_message(panic: &Box<dyn Any + Send>) -> String {
+    panic
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".into())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Holds if `func` returns without panicking.
+pub fn never_panics<R>(func: impl Fn(&str) -> R) -> impl Fn(&str) -> Result<(), String> {
+    move |input| {
+        func(input);
+        Ok(())
+    }
+}
+
+/// Holds if `func` returns the same answer as a `reference` implementation.
+pub fn agrees_with<R: PartialEq + Debug>(
+    func: impl Fn(&str) -> R,
+    reference: impl Fn(&str) -> R,
+) -> impl Fn(&str) -> Result<(), String> {
+    move |input| {
+        let (actual, expected) = (func(input), reference(input));
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(format!(
+                "expected {expected:?} (reference), found {actual:?}"
+            ))
+        }
+    }
+}
+
+/// Holds if `func` returns the same answer for the input and its `transform`, e.g. [`reverse_lines`].
+pub fn invariant_under<R: PartialEq + Debug>(
+    func: impl Fn(&str) -> R,
+    transform: impl Fn(&str) -> String,
+) -> impl Fn(&str) -> Result<(), String> {
+    move |input| {
+        let transformed = Input::from(transform(input));
+        let (original, changed) = (func(input), func(&transformed));
+        if original == changed {
+            Ok(())
+        } else {
+            Err(format!(
+                "answer changed from {original:?} to {changed:?} for transformed input:\n{transformed}"
+            ))
+        }
+    }
+}
+
+/// Reverses the order of lines within every blank-line separated block.
+pub fn reverse_lines(input: &str) -> String {
+    Input::from(input)
+        .blocks()
+        .map(|block| block.lines().rev().collect::<Vec<_>>().join("\n"))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        agrees_with, check, invariant_under, never_panics, parse_seed, reverse_lines, Rng,
+        DEFAULT_SEED,
+    };
+
+    #[test]
+    fn generates_deterministically() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        assert_eq!(
+            (0..10).map(|_| a.next_u64()).collect::<Vec<_>>(),
+            (0..10).map(|_| b.next_u64()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn seeds_deterministically_by_default() {
+        assert_eq!(parse_seed(None), DEFAULT_SEED);
+        assert_eq!(parse_seed(Some("")), DEFAULT_SEED);
+        assert_eq!(parse_seed(Some("42")), 42);
+        assert_ne!(parse_seed(Some("random")), DEFAULT_SEED);
+    }
+
+    #[test]
+    fn generates_within_range() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            let value = rng.range(-3..=3i8);
+            assert!((-3..=3).contains(&value));
+        }
+        assert_eq!(rng.range(5..=5usize), 5);
+    }
+
+    #[test]
+    fn shuffles_permutations() {
+        let mut rng = Rng::new(1);
+        let mut items: Vec<u32> = (0..20).collect();
+        rng.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn reverses_lines_per_block() {
+        assert_eq!(reverse_lines("a\nb\n\nc\nd\n"), "b\na\n\nd\nc");
+    }
+
+    #[test]
+    fn passes_holding_properties() {
+        let generate =
+            |rng: &mut Rng| rng.lines(1..=rng.size(), |rng| rng.range(0..=9u8).to_string());
+        let sum = |input: &str| {
+            input
+                .lines()
+                .map(|l| l.parse::<u32>().unwrap())
+                .sum::<u32>()
+        };
+        check(generate, never_panics(sum));
+        check(
+            generate,
+            agrees_with(sum, |input| {
+                input
+                    .bytes()
+                    .filter(u8::is_ascii_digit)
+                    .map(|b| u32::from(b - b'0'))
+                    .sum()
+            }),
+        );
+        check(generate, invariant_under(sum, reverse_lines));
+    }
+
+    #[test]
+    #[should_panic(expected = "reproduce with AOC_PROP_SEED=")]
+    fn reports_failing_input() {
+        let generate = |rng: &mut Rng| rng.lines(2..=3, |rng| rng.range(0..=9u8).to_string());
+        let first = |input: &str| input.lines().next().map(String::from);
+        check(generate, invariant_under(first, reverse_lines));
+    }
+
+    #[test]
+    #[should_panic(expected = "panicked: boom")]
+    fn reports_panics() {
+        check(|_| "1\n".into(), never_panics(|_| panic!("boom")));
+    }
+}
@@ -1,12 +1,172 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{env, fs, io, str::FromStr, time::Duration};
 
-use crate::template::timings::Timings;
+use crate::template::timings::{Timing, Timings};
 use crate::template::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// An optional column of the benchmark table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    /// Mean time of the day's `parse` function, benched on its own.
+    Parse,
+    /// Standard deviation of the bench samples, per part.
+    Stddev,
+    /// Number of bench samples, per part.
+    Samples,
+    /// Bytes allocated by a single run, per part.
+    Alloc,
+    /// Share of the day in the total runtime.
+    Share,
+    /// Average time of every alternative implementation, e.g. `P2 naive: 1.2ms`.
+    Variants,
+}
+
+impl Column {
+    fn header(self) -> &'static str {
+        match self {
+            Column::Parse => "Parse",
+            Column::Stddev => "Stddev",
+            Column::Samples => "Samples",
+            Column::Alloc => "Alloc",
+            Column::Share => "Share",
+            Column::Variants => "Variants",
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "parse" => Ok(Column::Parse),
+            "stddev" => Ok(Column::Stddev),
+            "samples" => Ok(Column::Samples),
+            "alloc" => Ok(Column::Alloc),
+            "share" => Ok(Column::Share),
+            "variants" => Ok(Column::Variants),
+            x => Err(format!(
+                "unknown column `{x}`, expecting one of parse, stddev, samples, alloc, share, variants."
+            )),
+        }
+    }
+}
+
+/// Order of the rows in the benchmark table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    #[default]
+    Day,
+    Slowest,
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "day" => Ok(SortOrder::Day),
+            "slowest" => Ok(SortOrder::Slowest),
+            x => Err(format!(
+                "unknown sort order `{x}`, expecting day or slowest."
+            )),
+        }
+    }
+}
+
+/// Unit that durations in the benchmark table are displayed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Unit {
+    /// Picks a unit per value, as printed by the runner.
+    #[default]
+    Auto,
+    Nanos,
+    Micros,
+    Millis,
+    Seconds,
+}
+
+impl Unit {
+    fn format(self, nanos: f64) -> String {
+        match self {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Unit::Auto => format!("{:.1?}", Duration::from_nanos(nanos.round() as u64)),
+            Unit::Nanos => format!("{nanos:.0}ns"),
+            Unit::Micros => format!("{:.2}µs", nanos / 1_000_f64),
+            Unit::Millis => format!("{:.2}ms", nanos / 1_000_000_f64),
+            Unit::Seconds => format!("{:.2}s", nanos / 1_000_000_000_f64),
+        }
+    }
+}
+
+impl FromStr for Unit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "auto" => Ok(Unit::Auto),
+            "ns" => Ok(Unit::Nanos),
+            "us" | "µs" => Ok(Unit::Micros),
+            "ms" => Ok(Unit::Millis),
+            "s" => Ok(Unit::Seconds),
+            x => Err(format!(
+                "unknown unit `{x}`, expecting one of auto, ns, us, ms, s."
+            )),
+        }
+    }
+}
+
+/// Configures the benchmark table. The defaults produce the classic Day / Part 1 / Part 2 table.
+#[derive(Debug, Clone, Default)]
+pub struct TableOptions {
+    pub columns: Vec<Column>,
+    pub sort: SortOrder,
+    pub unit: Unit,
+    pub highlight_slowest: bool,
+}
+
+impl TableOptions {
+    /// Reads options from the environment, e.g. as set in `.cargo/config.toml`:
+    ///  - `AOC_BENCH_COLUMNS`: comma-separated list of `parse`, `stddev`, `samples`, `alloc`, `share`, `variants`.
+    ///  - `AOC_BENCH_SORT`: `day` or `slowest`.
+    ///  - `AOC_BENCH_UNIT`: `auto`, `ns`, `us`, `ms` or `s`.
+    ///  - `AOC_BENCH_HIGHLIGHT`: `true` to highlight the slowest day.
+    ///
+    /// Invalid values are reported and replaced by their default.
+    pub fn from_env() -> Self {
+        let mut options = Self::default();
+
+        if let Ok(columns) = env::var("AOC_BENCH_COLUMNS") {
+            options.columns = columns
+                .split(',')
+                .filter(|c| !c.trim().is_empty())
+                .filter_map(|c| Column::from_str(c).map_err(|e| eprintln!("{e}")).ok())
+                .collect();
+        }
+
+        if let Ok(sort) = env::var("AOC_BENCH_SORT") {
+            options.sort = SortOrder::from_str(&sort)
+                .map_err(|e| eprintln!("{e}"))
+                .unwrap_or_default();
+        }
+
+        if let Ok(unit) = env::var("AOC_BENCH_UNIT") {
+            options.unit = Unit::from_str(&unit)
+                .map_err(|e| eprintln!("{e}"))
+                .unwrap_or_default();
+        }
+
+        if let Ok(highlight) = env::var("AOC_BENCH_HIGHLIGHT") {
+            options.highlight_slowest = matches!(highlight.trim(), "true" | "1" | "yes");
+        }
+
+        options
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -52,38 +212,158 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+fn format_part(timing: &Timing, part: u8, unit: Unit) -> String {
+    let value = match unit {
+        Unit::Auto => match part {
+            1 => timing.part_1.clone(),
+            _ => timing.part_2.clone(),
+        },
+        unit => timing.part_nanos(part).map(|nanos| unit.format(nanos)),
+    };
+    value.unwrap_or_else(|| "-".into())
+}
+
+fn format_bytes(bytes: u64) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let bytes_f = bytes as f64;
+    match bytes {
+        b if b < 1024 => format!("{b}B"),
+        b if b < 1024 * 1024 => format!("{:.1}KiB", bytes_f / 1024_f64),
+        _ => format!("{:.1}MiB", bytes_f / (1024 * 1024) as f64),
+    }
+}
+
+/// Formats a per-part value as `part 1 / part 2`.
+fn format_per_part(f: impl Fn(u8) -> Option<String>) -> String {
+    let values: Vec<String> = [1, 2]
+        .into_iter()
+        .map(|part| f(part).unwrap_or_else(|| "-".into()))
+        .collect();
+    values.join(" / ")
+}
+
+fn format_column(column: Column, timing: &Timing, total_nanos: f64, unit: Unit) -> String {
+    match column {
+        Column::Parse => timing
+            .parse_nanos
+            .map_or_else(|| "-".into(), |nanos| format!("`{}`", unit.format(nanos))),
+        Column::Stddev => format_per_part(|part| {
+            timing
+                .samples_for(part)
+                .map(|s| format!("`±{}`", unit.format(s.stddev_nanos)))
+        }),
+        Column::Samples => {
+            format_per_part(|part| timing.samples_for(part).map(|s| s.count.to_string()))
+        }
+        Column::Alloc => format_per_part(|part| {
+            timing
+                .samples_for(part)
+                .and_then(|s| s.alloc_bytes)
+                .map(format_bytes)
+        }),
+        Column::Share => {
+            if total_nanos > 0.0 {
+                format!("{:.1}%", timing.total_nanos / total_nanos * 100.0)
+            } else {
+                "-".into()
+            }
+        }
+        Column::Variants => {
+            let variants: Vec<String> = timing
+                .variants()
+                .map(|s| {
+                    format!(
+                        "P{} {}: `{}`",
+                        s.part,
+                        s.variant.as_deref().unwrap_or_default(),
+                        unit.format(s.mean_nanos)
+                    )
+                })
+                .collect();
+            if variants.is_empty() {
+                "-".into()
+            } else {
+                variants.join("<br>")
+            }
+        }
+    }
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: Timings,
+    total_millis: f64,
+    options: &TableOptions,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut header_row = "| Day | Part 1 | Part 2 |".to_string();
+    let mut align_row = "| :---: | :---: | :---: ".to_string();
+    for column in &options.columns {
+        header_row.push_str(&format!(" {} |", column.header()));
+        align_row.push_str("| :---: ");
+    }
+    align_row.push_str(" |");
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new(), header_row, align_row];
 
-    for timing in timings.data {
+    let machines = timings.machines().len();
+    let mut data = timings.data;
+    if options.sort == SortOrder::Slowest {
+        data.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    }
+
+    let total_nanos: f64 = data.iter().map(|t| t.total_nanos).sum();
+    let slowest = data
+        .iter()
+        .max_by(|a, b| a.total_nanos.total_cmp(&b.total_nanos))
+        .map(|t| t.day);
+
+    for timing in &data {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        let mut day = format!("[Day {}]({})", timing.day.into_inner(), path);
+        if options.highlight_slowest && slowest == Some(timing.day) {
+            day = format!("**{day}** 🐢");
+        }
+
+        let mut line = format!(
+            "| {} | `{}` | `{}` |",
+            day,
+            format_part(timing, 1, options.unit),
+            format_part(timing, 2, options.unit)
+        );
+        for column in &options.columns {
+            line.push_str(&format!(
+                " {} |",
+                format_column(*column, timing, total_nanos, options.unit)
+            ));
+        }
+        lines.push(line);
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    if machines > 1 {
+        lines.push(String::new());
+        lines.push(format!(
+            "> ⚠️ These timings were recorded on {} different machines and are not directly comparable.",
+            machines
+        ));
+    }
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    options: &TableOptions,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", timings, total_millis, options);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
@@ -92,15 +372,25 @@ pub fn update(timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(
+        &mut readme,
+        timings,
+        total_millis,
+        &TableOptions::from_env(),
+    )?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{update_content, Column, SortOrder, TableOptions, Unit, MARKER};
+    use crate::{
+        day,
+        template::fingerprint::Fingerprint,
+        template::timings::Timings,
+        template::timings::{Samples, Timing, TimingMode},
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -110,18 +400,30 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    parse_nanos: None,
+                    samples: vec![],
+                    fingerprint: None,
+                    failures: vec![],
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    parse_nanos: None,
+                    samples: vec![],
+                    fingerprint: None,
+                    failures: vec![],
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    parse_nanos: None,
+                    samples: vec![],
+                    fingerprint: None,
+                    failures: vec![],
                 },
             ],
         }
@@ -131,28 +433,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +462,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +482,93 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn warns_about_mixed_machines() {
+        let fingerprint = |cpu: &str| Fingerprint {
+            cpu: cpu.into(),
+            cores: 8,
+            kernel: "Linux 6.1".into(),
+            rustc: "rustc 1.83.0".into(),
+            target: "x86_64-unknown-linux-gnu".into(),
+            profile: "release".into(),
+            opt_level: "3".into(),
+            features: vec![],
+        };
+
+        let mut timings = get_mock_timings();
+        timings.data[0].fingerprint = Some(fingerprint("Laptop"));
+        timings.data[1].fingerprint = Some(fingerprint("Laptop"));
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings.clone(), 190.0, &TableOptions::default()).unwrap();
+        assert_eq!(s.contains("different machines"), false);
+
+        timings.data[2].fingerprint = Some(fingerprint("Workstation"));
+        update_content(&mut s, timings, 190.0, &TableOptions::default()).unwrap();
+        assert_eq!(
+            s.contains("recorded on 2 different machines and are not directly comparable"),
+            true
+        );
+    }
+
+    #[test]
+    fn format_configured_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[0].parse_nanos = Some(1500.0);
+        timings.data[0].samples = vec![
+            Samples {
+                part: 1,
+                variant: None,
+                count: 100,
+                mean_nanos: 1e+7,
+                stddev_nanos: 2e+5,
+                alloc_bytes: Some(2048),
+                mode: TimingMode::Warm,
+                quantiles: vec![],
+            },
+            Samples {
+                part: 1,
+                variant: Some("naive".into()),
+                count: 10,
+                mean_nanos: 5e+7,
+                stddev_nanos: 1e+6,
+                alloc_bytes: None,
+                mode: TimingMode::Reset,
+                quantiles: vec![],
+            },
+        ];
+
+        let options = TableOptions {
+            columns: vec![
+                Column::Parse,
+                Column::Stddev,
+                Column::Samples,
+                Column::Alloc,
+                Column::Share,
+                Column::Variants,
+            ],
+            sort: SortOrder::Slowest,
+            unit: Unit::Millis,
+            highlight_slowest: true,
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &options).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 | Parse | Stddev | Samples | Alloc | Share | Variants |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---:  |",
+            "| **[Day 4](./src/bin/04.rs)** 🐢 | `40.00ms` | `50.00ms` | - | - / - | - / - | - / - | 47.4% | - |",
+            "| [Day 2](./src/bin/02.rs) | `30.00ms` | `40.00ms` | - | - / - | - / - | - / - | 36.8% | - |",
+            "| [Day 1](./src/bin/01.rs) | `10.00ms` | `20.00ms` | `0.00ms` | `±0.20ms` / - | 100 / - | 2.0KiB / - | 15.8% | P1 naive: `50.00ms` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
 }
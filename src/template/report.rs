@@ -0,0 +1,443 @@
+/// Module that renders timings as a self-contained HTML page with inline SVG charts.
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{fs, io};
+
+use crate::template::timings::{Samples, Timing, Timings};
+
+static REPORT_FILE_NAME: &str = "index.html";
+
+const PART_COLORS: [&str; 2] = ["#4e79a7", "#f28e2b"];
+
+const CHART_WIDTH: f64 = 760.0;
+const LABEL_WIDTH: f64 = 110.0;
+const ROW_HEIGHT: f64 = 26.0;
+const AXIS_HEIGHT: f64 = 24.0;
+/// Space right of the plot area that keeps value labels inside the chart.
+const VALUE_WIDTH: f64 = 70.0;
+
+/// Writes the report for `timings` to `<dir>/index.html`, creating `dir` if needed.
+pub fn write(timings: &Timings, dir: &Path) -> Result<PathBuf, io::Error> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(REPORT_FILE_NAME);
+    fs::write(&path, render(timings))?;
+    Ok(path)
+}
+
+fn render(timings: &Timings) -> String {
+    let mut html = String::new();
+
+    html.push_str(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+        <title>Advent of Code Benchmarks</title>\n<style>\n\
+        body { font-family: system-ui, sans-serif; max-width: 820px; margin: 2em auto; color: #222; }\n\
+        svg { display: block; margin: 1em 0; }\n\
+        svg text { font-size: 12px; fill: #222; }\n\
+        .grid { stroke: #ddd; }\n\
+        table { border-collapse: collapse; }\n\
+        th, td { padding: 0.25em 0.75em; text-align: right; border-bottom: 1px solid #ddd; }\n\
+        .legend span { display: inline-block; width: 0.8em; height: 0.8em; margin: 0 0.3em 0 1em; }\n\
+        </style>\n</head>\n<body>\n",
+    );
+
+    let _ = writeln!(html, "<h1>Benchmarks</h1>");
+    let _ = writeln!(
+        html,
+        "<p><strong>Total: {:.2}ms</strong> across {} days.</p>",
+        timings.total_millis(),
+        timings.data.len()
+    );
+    let _ = writeln!(
+        html,
+        "<p class=\"legend\"><span style=\"background: {}\"></span>Part 1\
+        <span style=\"background: {}\"></span>Part 2</p>",
+        PART_COLORS[0], PART_COLORS[1]
+    );
+
+    let _ = writeln!(html, "<h2>Runtime per day</h2>");
+    html.push_str(&day_chart(timings));
+
+    let _ = writeln!(html, "<h2>Part breakdown</h2>");
+    html.push_str(&breakdown_chart(timings));
+    html.push_str(&breakdown_table(timings));
+
+    let _ = writeln!(html, "<h2>Sample distributions</h2>");
+    html.push_str(&distribution_chart(timings));
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Maps durations in nanoseconds onto the x axis using a base 10 logarithm.
+struct LogScale {
+    min_exp: i32,
+    max_exp: i32,
+}
+
+impl LogScale {
+    fn new(values: impl Iterator<Item = f64>) -> Self {
+        let (min, max) = values
+            .filter(|v| *v > 0.0)
+            .fold((f64::MAX, f64::MIN), |(min, max), v| {
+                (min.min(v), max.max(v))
+            });
+
+        if min > max {
+            return Self {
+                min_exp: 0,
+                max_exp: 1,
+            };
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        let min_exp = min.log10().floor() as i32;
+        #[allow(clippy::cast_possible_truncation)]
+        let max_exp = (max.log10().ceil() as i32).max(min_exp + 1);
+        Self { min_exp, max_exp }
+    }
+
+    fn x(&self, nanos: f64) -> f64 {
+        let span = f64::from(self.max_exp - self.min_exp);
+        let pos = (nanos.max(1.0).log10() - f64::from(self.min_exp)) / span;
+        LABEL_WIDTH + pos.clamp(0.0, 1.0) * (CHART_WIDTH - LABEL_WIDTH - VALUE_WIDTH)
+    }
+
+    fn axis(&self, top: f64, bottom: f64) -> String {
+        let mut svg = String::new();
+        for exp in self.min_exp..=self.max_exp {
+            let nanos = 10_f64.powi(exp);
+            let x = self.x(nanos);
+            let _ = writeln!(
+                svg,
+                "<line class=\"grid\" x1=\"{x:.1}\" y1=\"{top:.1}\" x2=\"{x:.1}\" y2=\"{bottom:.1}\"/>\
+                <text x=\"{x:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>",
+                bottom + 16.0,
+                format_nanos(nanos, 0),
+            );
+        }
+        svg
+    }
+}
+
+fn format_nanos(nanos: f64, precision: usize) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.round() as u64);
+    format!("{duration:.precision$?}")
+}
+
+fn svg_open(height: f64) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{CHART_WIDTH}\" height=\"{height:.0}\" \
+        viewBox=\"0 0 {CHART_WIDTH} {height:.0}\">\n"
+    )
+}
+
+fn row_label(y: f64, label: &str) -> String {
+    format!(
+        "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{label}</text>\n",
+        LABEL_WIDTH - 8.0,
+        y + ROW_HEIGHT / 2.0 + 4.0
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Horizontal bars with the total runtime of each day, stacked by part on a log scale.
+fn day_chart(timings: &Timings) -> String {
+    let scale = LogScale::new(timings.data.iter().map(|t| t.total_nanos));
+    #[allow(clippy::cast_precision_loss)]
+    let height = timings.data.len() as f64 * ROW_HEIGHT + AXIS_HEIGHT + 4.0;
+    let rows_bottom = height - AXIS_HEIGHT;
+
+    let mut svg = svg_open(height);
+    svg.push_str(&scale.axis(0.0, rows_bottom));
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = i as f64 * ROW_HEIGHT;
+        svg.push_str(&row_label(y, &format!("Day {}", timing.day)));
+
+        // NOTE: parts are stacked in linear time, so part 2's share is read off the log axis.
+        let part_1 = timing.part_nanos(1).unwrap_or(0.0);
+        let split = scale.x(part_1).max(LABEL_WIDTH);
+        let end = scale.x(timing.total_nanos);
+
+        for (part, (x1, x2)) in [(LABEL_WIDTH, split), (split, end)].into_iter().enumerate() {
+            if x2 > x1 {
+                let _ = writeln!(
+                    svg,
+                    "<rect x=\"{x1:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"/>",
+                    y + 4.0,
+                    x2 - x1,
+                    ROW_HEIGHT - 8.0,
+                    PART_COLORS[part]
+                );
+            }
+        }
+
+        let _ = writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\">{}</text>",
+            end + 4.0,
+            y + ROW_HEIGHT / 2.0 + 4.0,
+            format_nanos(timing.total_nanos, 1)
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// 100% stacked bars with the share of each part in a day's runtime.
+fn breakdown_chart(timings: &Timings) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let height = timings.data.len() as f64 * ROW_HEIGHT + 4.0;
+    let width = CHART_WIDTH - LABEL_WIDTH - 10.0;
+
+    let mut svg = svg_open(height);
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = i as f64 * ROW_HEIGHT;
+        svg.push_str(&row_label(y, &format!("Day {}", timing.day)));
+
+        let parts = [
+            timing.part_nanos(1).unwrap_or(0.0),
+            timing.part_nanos(2).unwrap_or(0.0),
+        ];
+        let total: f64 = parts.iter().sum();
+        if total <= 0.0 {
+            continue;
+        }
+
+        let mut x = LABEL_WIDTH;
+        for (part, nanos) in parts.into_iter().enumerate() {
+            let w = nanos / total * width;
+            if w > 0.0 {
+                let _ = writeln!(
+                    svg,
+                    "<rect x=\"{x:.1}\" y=\"{:.1}\" width=\"{w:.1}\" height=\"{:.1}\" fill=\"{}\">\
+                    <title>Part {}: {} ({:.1}%)</title></rect>",
+                    y + 4.0,
+                    ROW_HEIGHT - 8.0,
+                    PART_COLORS[part],
+                    part + 1,
+                    format_nanos(nanos, 1),
+                    nanos / total * 100.0
+                );
+            }
+            x += w;
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn breakdown_table(timings: &Timings) -> String {
+    let total = timings.data.iter().map(|t| t.total_nanos).sum::<f64>();
+
+    let mut html = String::from(
+        "<table>\n<tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Total</th><th>Share</th></tr>\n",
+    );
+
+    for timing in &timings.data {
+        let share = if total > 0.0 {
+            timing.total_nanos / total * 100.0
+        } else {
+            0.0
+        };
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{share:.1}%</td></tr>",
+            timing.day,
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-"),
+            format_nanos(timing.total_nanos, 1),
+        );
+    }
+
+    html.push_str("</table>\n");
+    html
+}
+
+/// Violin and box plots of the sample distribution of every benched part.
+fn distribution_chart(timings: &Timings) -> String {
+    let rows: Vec<(&Timing, &Samples)> = timings
+        .data
+        .iter()
+        .flat_map(|t| t.samples.iter().map(move |s| (t, s)))
+        .collect();
+
+    if rows.is_empty() {
+        return "<p>No sample data stored. Run <code>cargo time --store</code> to record it.</p>\n"
+            .into();
+    }
+
+    let scale = LogScale::new(rows.iter().flat_map(|(_, s)| [s.min(), s.max()]));
+    #[allow(clippy::cast_precision_loss)]
+    let height = rows.len() as f64 * ROW_HEIGHT + AXIS_HEIGHT + 4.0;
+    let rows_bottom = height - AXIS_HEIGHT;
+
+    let mut svg = svg_open(height);
+    svg.push_str(&scale.axis(0.0, rows_bottom));
+
+    for (i, (timing, samples)) in rows.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = i as f64 * ROW_HEIGHT;
+        let mid = y + ROW_HEIGHT / 2.0;
+        let color = PART_COLORS[usize::from(samples.part.clamp(1, 2) - 1)];
+
+        svg.push_str(&row_label(
+            y,
+            &match &samples.variant {
+                Some(variant) => format!("Day {} · Part {} ({variant})", timing.day, samples.part),
+                None => format!("Day {} · Part {}", timing.day, samples.part),
+            },
+        ));
+        svg.push_str(&violin(samples, &scale, mid, color));
+
+        let (q1, median, q3) = (
+            scale.x(samples.quantile(0.25)),
+            scale.x(samples.median()),
+            scale.x(samples.quantile(0.75)),
+        );
+        let _ = writeln!(
+            svg,
+            "<line x1=\"{:.1}\" y1=\"{mid:.1}\" x2=\"{:.1}\" y2=\"{mid:.1}\" stroke=\"#222\"/>\
+            <rect x=\"{q1:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"6\" fill=\"#fff\" stroke=\"#222\"/>\
+            <line x1=\"{median:.1}\" y1=\"{:.1}\" x2=\"{median:.1}\" y2=\"{:.1}\" stroke=\"#222\" stroke-width=\"2\"/>\
+            <title>min {} · median {} · max {} · {} samples</title>",
+            scale.x(samples.min()),
+            scale.x(samples.max()),
+            mid - 3.0,
+            (q3 - q1).max(1.0),
+            mid - 3.0,
+            mid + 3.0,
+            format_nanos(samples.min(), 1),
+            format_nanos(samples.median(), 1),
+            format_nanos(samples.max(), 1),
+            samples.count,
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Approximates the sample density between adjacent quantiles, mirrored around `mid`.
+fn violin(samples: &Samples, scale: &LogScale, mid: f64, color: &str) -> String {
+    let xs: Vec<f64> = samples.quantiles.iter().map(|q| scale.x(*q)).collect();
+    let densities: Vec<(f64, f64)> = xs
+        .windows(2)
+        .map(|w| ((w[0] + w[1]) / 2.0, 1.0 / (w[1] - w[0]).max(0.5)))
+        .collect();
+    let max_density = densities.iter().map(|d| d.1).fold(0.0, f64::max);
+
+    if densities.is_empty() || max_density <= 0.0 {
+        return String::new();
+    }
+
+    let half_height = ROW_HEIGHT / 2.0 - 2.0;
+    let outline: Vec<(f64, f64)> = std::iter::once((xs[0], 0.0))
+        .chain(
+            densities
+                .iter()
+                .map(|(x, d)| (*x, d / max_density * half_height)),
+        )
+        .chain(std::iter::once((xs[xs.len() - 1], 0.0)))
+        .collect();
+
+    let points: Vec<String> = outline
+        .iter()
+        .map(|(x, h)| format!("{x:.1},{:.1}", mid - h))
+        .chain(
+            outline
+                .iter()
+                .rev()
+                .map(|(x, h)| format!("{x:.1},{:.1}", mid + h)),
+        )
+        .collect();
+
+    format!(
+        "<polygon points=\"{}\" fill=\"{color}\" fill-opacity=\"0.5\"/>\n",
+        points.join(" ")
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, LogScale};
+    use crate::{
+        day,
+        template::timings::{Samples, Timing, TimingMode, Timings},
+    };
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some("10.0µs".into()),
+                    part_2: Some("20.0µs".into()),
+                    total_nanos: 3e+4,
+                    parse_nanos: None,
+                    samples: vec![Samples {
+                        part: 1,
+                        variant: None,
+                        count: 5,
+                        mean_nanos: 1e+4,
+                        stddev_nanos: 1e+3,
+                        alloc_bytes: None,
+                        mode: TimingMode::Warm,
+                        quantiles: vec![9e+3, 9.5e+3, 1e+4, 1.1e+4, 1.2e+4],
+                    }],
+                    fingerprint: None,
+                    failures: vec![],
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some("1.0ms".into()),
+                    part_2: None,
+                    total_nanos: 1e+6,
+                    parse_nanos: None,
+                    samples: vec![],
+                    fingerprint: None,
+                    failures: vec![],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn log_scale_spans_powers_of_ten() {
+        let scale = LogScale::new([30.0, 4000.0].into_iter());
+        assert_eq!(scale.min_exp, 1);
+        assert_eq!(scale.max_exp, 4);
+        assert!(scale.x(100.0) < scale.x(1000.0));
+    }
+
+    #[test]
+    fn renders_self_contained_page() {
+        let html = render(&get_mock_timings());
+        assert_eq!(html.starts_with("<!DOCTYPE html>"), true);
+        assert_eq!(html.contains("<script"), false);
+        assert_eq!(html.contains("<link"), false);
+        assert_eq!(html.matches("<svg").count(), 3);
+        assert_eq!(html.contains("Day 01 · Part 1"), true);
+        assert_eq!(html.contains("<td>02</td><td>1.0ms</td><td>-</td>"), true);
+    }
+
+    #[test]
+    fn renders_without_samples() {
+        let mut timings = get_mock_timings();
+        timings.data.iter_mut().for_each(|t| t.samples.clear());
+        let html = render(&timings);
+        assert_eq!(html.contains("No sample data stored"), true);
+    }
+}
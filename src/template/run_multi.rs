@@ -7,7 +7,9 @@ use super::{
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Runs the solutions of `days_to_run`. Timings are only meaningful if `is_timed` is set,
+/// but failures are recorded either way.
+pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Timings {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
@@ -34,16 +36,33 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             }
         });
 
+    let timings = Timings { data: timings };
+
     if is_timed {
-        let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
     }
+
+    if timings.has_failures() {
+        println!("\n{ANSI_BOLD}Failed:{ANSI_RESET}");
+        for timing in &timings.data {
+            for failure in &timing.failures {
+                let variant = failure
+                    .variant
+                    .as_ref()
+                    .map_or(String::new(), |v| format!(" [{v}]"));
+                let message = failure.message.lines().next().unwrap_or_default();
+                match failure.part {
+                    0 => println!("Day {}: {message}", timing.day),
+                    part => println!("Day {} Part {part}{variant}: {message}", timing.day),
+                }
+            }
+        }
+    }
+
+    timings
 }
 
 #[allow(dead_code)]
@@ -68,11 +87,17 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
+    use crate::template::fingerprint::Fingerprint;
+    use crate::template::timings::{
+        is_report_line, parse_nanos, Failure, Samples, FAILURE_MARKER, FINGERPRINT_MARKER,
+        PARSE_MARKER, REPORT_ENV, SAMPLES_MARKER,
+    };
     use crate::template::Day;
     use std::{
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
+        str::FromStr,
         thread,
     };
 
@@ -101,6 +126,7 @@ pub mod child_commands {
 
         let mut cmd = Command::new("cargo")
             .args(&args)
+            .env(REPORT_ENV, "1")
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -118,12 +144,25 @@ pub mod child_commands {
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
+            // report lines are machine-readable, don't forward them to the user.
+            if !is_report_line(&line) {
+                println!("{line}");
+            }
             output.push(line);
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
+
+        // a panic or an early `exit` skips the failure lines of the parts, so fail the whole day.
+        if !status.success() {
+            let failure = Failure {
+                part: 0,
+                variant: None,
+                message: format!("solution failed ({status})"),
+            };
+            output.push(failure.to_string());
+        }
 
         Ok(output)
     }
@@ -134,8 +173,43 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            parse_nanos: None,
+            samples: vec![],
+            fingerprint: None,
+            failures: vec![],
         };
 
+        timings.samples = output
+            .iter()
+            .filter(|l| l.starts_with(SAMPLES_MARKER))
+            .filter_map(|l| match Samples::from_str(l) {
+                Ok(samples) => Some(samples),
+                Err(e) => {
+                    eprintln!("Could not parse samples from line: {l} ({e})");
+                    None
+                }
+            })
+            .collect();
+
+        timings.failures = output
+            .iter()
+            .filter(|l| l.starts_with(FAILURE_MARKER))
+            .filter_map(|l| Failure::from_str(l).ok())
+            .collect();
+
+        timings.parse_nanos = output.iter().find_map(|l| {
+            l.strip_prefix(PARSE_MARKER)?
+                .trim()
+                .strip_prefix("nanos=")?
+                .parse()
+                .ok()
+        });
+
+        timings.fingerprint = output.iter().find_map(|l| {
+            let json = l.strip_prefix(FINGERPRINT_MARKER)?.trim().parse().ok()?;
+            Fingerprint::try_from(&json).ok()
+        });
+
         output
             .iter()
             .filter_map(|l| {
@@ -143,12 +217,17 @@ pub mod child_commands {
                     return None;
                 }
 
+                let part = l.split(':').next()?;
+                // the parse time is stored on its own, and alternative implementations with the
+                // samples only.
+                if !part.starts_with("Part") || part.contains('[') {
+                    return None;
+                }
+
                 let Some((timing_str, nanos)) = parse_time(l) else {
                     eprintln!("Could not parse timings from line: {l}");
                     return None;
                 };
-
-                let part = l.split(':').next()?;
                 Some((part, timing_str, nanos))
             })
             .for_each(|(part, timing_str, nanos)| {
@@ -164,12 +243,7 @@ pub mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
@@ -179,12 +253,7 @@ pub mod child_commands {
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let parsed_timing = parse_nanos(str_timing)?;
 
         Some((str_timing, parsed_timing))
     }
@@ -207,7 +276,7 @@ pub mod child_commands {
     mod tests {
         use super::parse_exec_time;
 
-        use crate::day;
+        use crate::{day, template::timings::TimingMode};
 
         #[test]
         fn parses_execution_times() {
@@ -224,6 +293,95 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "74.13ms");
         }
 
+        #[test]
+        fn parses_samples() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: (1.5µs @ 10 samples)".into(),
+                    "Part 1: 0 (2.0ns @ 3 samples)".into(),
+                    "::samples:: part=1 count=3 mean=2 stddev=0.8 quantiles=1,2,3".into(),
+                    "::parse:: nanos=1500".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 2_f64);
+            assert_eq!(res.samples.len(), 1);
+            assert_eq!(res.samples[0].part, 1);
+            assert_eq!(res.samples[0].quantiles, vec![1.0, 2.0, 3.0]);
+            assert_eq!(res.parse_nanos, Some(1500.0));
+            assert_eq!(res.fingerprint, None);
+            assert_eq!(res.samples[0].mode, TimingMode::Warm);
+        }
+
+        #[test]
+        fn parses_timing_mode() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (2.0ns @ 3 samples)".into(),
+                    "::samples:: part=1 count=3 mean=2 stddev=0.8 quantiles=1,2,3".into(),
+                    "Part 2: 0 (2.0ns @ 3 samples)".into(),
+                    "::samples:: part=2 count=3 mean=2 stddev=0.8 mode=reset quantiles=1,2,3"
+                        .into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.samples[0].mode, TimingMode::Warm);
+            assert_eq!(res.samples[1].mode, TimingMode::Reset);
+        }
+
+        #[test]
+        fn ignores_variants_in_totals() {
+            let res = parse_exec_time(
+                &[
+                    "Part 2: 4 (2.0ns @ 3 samples)".into(),
+                    "::samples:: part=2 count=3 mean=2 stddev=0 quantiles=1,2,3".into(),
+                    "Part 2 [naive]: 4 (5.0ns @ 3 samples)".into(),
+                    "::samples:: part=2 variant=naive count=3 mean=5 stddev=0 quantiles=4,5,6"
+                        .into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 2_f64);
+            assert_eq!(res.part_2.as_deref(), Some("2.0ns"));
+            assert_eq!(res.samples.len(), 2);
+            assert_eq!(res.samples_for(2).unwrap().mean_nanos, 2.0);
+            assert_eq!(res.variants().count(), 1);
+        }
+
+        #[test]
+        fn parses_failures() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: ✖ invalid digit".into(),
+                    "::failure:: part=1 message=invalid digit".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1, None);
+            assert_eq!(res.failures.len(), 1);
+            assert_eq!(res.failures[0].part, 1);
+            assert_eq!(res.failures[0].message, "invalid digit");
+        }
+
+        #[test]
+        fn parses_fingerprint() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (2.0ns @ 3 samples)".into(),
+                    r#"::fingerprint:: {"cpu":"Mock CPU","cores":8,"kernel":"Linux 6.1","rustc":"rustc 1.83.0","target":"x86_64-unknown-linux-gnu","profile":"release","opt_level":"3","features":[]}"#.into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            let fingerprint = res.fingerprint.unwrap();
+            assert_eq!(fingerprint.cpu, "Mock CPU");
+            assert_eq!(fingerprint.cores, 8);
+        }
+
         #[test]
         fn parses_with_patterns_in_input() {
             let res = parse_exec_time(
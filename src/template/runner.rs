@@ -6,53 +6,273 @@ use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{env, process};
 
+use crate::template::alloc::{count_allocs, AllocStats};
+use crate::template::fingerprint::Fingerprint;
+use crate::template::timings::{
+    Failure, Samples, TimingMode, FINGERPRINT_MARKER, PARSE_MARKER, REPORT_ENV,
+};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, ocr, Day, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// The return type of a solution part: either `Option<T>` or `Result<T, E>`.
+pub trait PartResult {
+    type Answer: Display + PartialEq;
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    fn outcome(&self) -> Outcome<'_, Self::Answer>;
+}
+
+/// What a solution part returned, independent of its return type.
+#[derive(Debug, PartialEq)]
+pub enum Outcome<'a, T> {
+    Solved(&'a T),
+    /// The part returned `None`, e.g. because it is not implemented yet.
+    Unsolved,
+    /// The part returned an error, holding its message.
+    Failed(String),
+}
+
+impl<T: Display + PartialEq> PartResult for Option<T> {
+    type Answer = T;
+
+    fn outcome(&self) -> Outcome<'_, T> {
+        self.as_ref().map_or(Outcome::Unsolved, Outcome::Solved)
+    }
+}
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+impl<T: Display + PartialEq, E: Display> PartResult for Result<T, E> {
+    type Answer = T;
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    fn outcome(&self) -> Outcome<'_, T> {
+        match self {
+            Ok(answer) => Outcome::Solved(answer),
+            Err(e) => Outcome::Failed(e.to_string()),
+        }
+    }
+}
+
+pub fn run_part<I: Copy, R: PartResult>(func: impl Fn(I) -> R, input: I, day: Day, part: u8) {
+    let result = run_variant(func, input, day, part, None, None);
+    submit_outcome(&result.outcome(), day, part);
+}
+
+/// Run every implementation of a part and exit with an error if their answers disagree.
+///
+/// The first implementation is the primary one: its answer is submitted and its timing is the
+/// one stored for the part. The others are labelled with their name in the output and samples.
+///
+/// `reset` is called before every run, so that state left behind by one run does not speed up
+/// the next one.
+pub fn run_variants<I: Copy, R: PartResult>(
+    names: &[&str],
+    funcs: &[impl Fn(I) -> R],
+    input: I,
+    day: Day,
+    part: u8,
+    reset: Option<fn()>,
+) {
+    let results: Vec<R> = names
+        .iter()
+        .zip(funcs)
+        .enumerate()
+        .map(|(i, (name, func))| {
+            run_variant(func, input, day, part, (i > 0).then_some(*name), reset)
+        })
+        .collect();
+
+    let outcomes: Vec<Outcome<R::Answer>> = results.iter().map(PartResult::outcome).collect();
+
+    if outcomes.iter().any(|outcome| outcome != &outcomes[0]) {
+        let answers: Vec<String> = names
+            .iter()
+            .zip(&outcomes)
+            .map(|(name, outcome)| match outcome {
+                Outcome::Solved(answer) => format!("{name} = {answer}"),
+                Outcome::Unsolved => format!("{name} = ✖"),
+                Outcome::Failed(e) => format!("{name} = ✖ ({e})"),
+            })
+            .collect();
+        eprintln!(
+            "Part {part}: implementations disagree: {}",
+            answers.join(", ")
+        );
+        process::exit(1);
+    }
+
+    submit_outcome(&outcomes[0], day, part);
+}
+
+#[cfg_attr(not(feature = "cpu-profile"), allow(unused_variables))]
+fn run_variant<I: Copy, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    day: Day,
+    part: u8,
+    variant: Option<&str>,
+    reset: Option<fn()>,
+) -> R {
+    let mode = match reset {
+        Some(_) => TimingMode::Reset,
+        None => TimingMode::Warm,
+    };
+    let reset = move || {
+        if let Some(reset) = reset {
+            reset();
+        }
+    };
+
+    let part_str = match variant {
+        Some(variant) => format!("Part {part} [{variant}]"),
+        None => format!("Part {part}"),
+    };
+
+    let _trace_part = crate::trace::part(part, variant);
+    #[cfg(feature = "viz")]
+    crate::viz::record();
+    let (result, duration, samples, timers, allocs) = run_timed(&func, input, reset, |result| {
+        let outcome = result.outcome();
+        print_result(&outcome, &part_str, "");
+        // only answers are worth benching.
+        matches!(outcome, Outcome::Solved(_))
+    });
+
+    print_result(
+        &result.outcome(),
+        &part_str,
+        &format_duration(&duration, samples),
+    );
+
+    #[cfg(feature = "viz")]
+    crate::viz::play_queued();
+
+    // report failures and the sample distribution to `run_multi`, which records them with the timings.
+    if let (Outcome::Failed(message), true) = (result.outcome(), is_reporting()) {
+        println!(
+            "{}",
+            Failure {
+                part,
+                variant: variant.map(String::from),
+                message,
+            }
+        );
+    }
+
+    if let Some(mut samples) = Samples::from_durations(part, &timers).filter(|_| is_reporting()) {
+        samples.variant = variant.map(String::from);
+        samples.alloc_bytes = allocs.map(|a| a.bytes);
+        samples.mode = mode;
+        println!("{samples}");
+    }
+
+    #[cfg(feature = "cpu-profile")]
+    if matches!(result.outcome(), Outcome::Solved(_)) {
+        let name = match variant {
+            Some(variant) => format!("part{part}-{variant}"),
+            None => format!("part{part}"),
+        };
+        #[cfg(feature = "viz")]
+        let _paused = crate::viz::pause();
+        let _quiet = (crate::trace::pause(), crate::memo::pause());
+        let func = |input| {
+            reset();
+            func(input)
+        };
+        match crate::template::profiler::profile(func, input, day, &name) {
+            Ok(dir) => println!("\r{part_str}: profile written to {}", dir.display()),
+            Err(e) => println!("\r{part_str}: {e}"),
+        }
+    }
+
+    result
+}
+
+/// Whether the solution runs under `run_multi`, which reads the report lines it prints. They are
+/// never shown to the user.
+fn is_reporting() -> bool {
+    env::var_os(REPORT_ENV).is_some()
+}
+
+/// Bench the day's `parse` function on its own when timing solutions, and report its mean time
+/// to `run_multi`. Parts still parse the input themselves, so their timings include this time.
+pub fn run_parse<I: Copy, T>(func: impl Fn(I) -> T, input: I) {
+    if !std::env::args().any(|x| x == "--time") {
+        return;
+    }
+
+    print!("Parse:");
+    let (duration, samples, _) = bench(func, input, || {}, WARMUP_TIME, BENCH_TIME);
+    print!("\r");
+    println!("Parse:{}", format_duration(&duration, samples));
+
+    if is_reporting() {
+        println!("{PARSE_MARKER} nanos={}", duration.as_nanos());
+    }
+}
+
+/// Report the machine and build the solution runs with to `run_multi` when timing solutions.
+pub fn report_fingerprint() {
+    if is_reporting() && std::env::args().any(|x| x == "--time") {
+        let json = tinyjson::JsonValue::from(&Fingerprint::current());
+        if let Ok(json) = json.stringify() {
+            println!("{FINGERPRINT_MARKER} {json}");
+        }
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Returns the individual bench timers alongside the average, these are empty if the part was not benched.
+/// The allocations of the first run are returned if they were counted.
+///
+/// `reset` is called before each run, outside of the timer.
+/// `hook` is called with the result of the first run and returns whether the part should be benched.
+#[allow(clippy::type_complexity)]
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    reset: impl Fn(),
+    hook: impl Fn(&T) -> bool,
+) -> (T, Duration, u128, Vec<Duration>, Option<AllocStats>) {
+    reset();
     let timer = Instant::now();
-    let result = {
+    let (result, allocs) = {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        count_allocs(|| func(input))
     };
     let base_time = timer.elapsed();
 
-    hook(&result);
+    let should_bench = hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input)
+    let run = if should_bench && std::env::args().any(|x| x == "--time") {
+        bench(func, input, reset, WARMUP_TIME, BENCH_TIME)
     } else {
-        (base_time, 1)
+        (base_time, 1, vec![])
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, run.2, allocs)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I) -> (Duration, u128) {
+/// How long a part runs before and while it is benched.
+const WARMUP_TIME: Duration = Duration::from_secs(1);
+const BENCH_TIME: Duration = Duration::from_secs(3);
+
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    reset: impl Fn(),
+    warmup_time: Duration,
+    bench_time: Duration,
+) -> (Duration, u128, Vec<Duration>) {
     let mut stdout = stdout();
 
+    // frames were recorded by the first run already.
+    #[cfg(feature = "viz")]
+    let _paused = crate::viz::pause();
+    let _quiet = (crate::trace::pause(), crate::memo::pause());
+
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
@@ -62,18 +282,20 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I) -> (Duration, u128) {
 
     let warmup_timer = Instant::now();
     loop {
+        reset();
         black_box(func(black_box(input)));
-        if warmup_timer.elapsed() > Duration::from_secs(1) {
+        if warmup_timer.elapsed() > warmup_time {
             break;
         }
     }
     let bench_timer = Instant::now();
     loop {
+        reset();
         let timer = Instant::now();
         black_box(func(black_box(input)));
         timers.push(timer.elapsed());
         bench_iterations += 1;
-        if bench_timer.elapsed() > Duration::from_secs(3) {
+        if bench_timer.elapsed() > bench_time {
             break;
         }
     }
@@ -82,6 +304,7 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I) -> (Duration, u128) {
         #[allow(clippy::cast_possible_truncation)]
         Duration::from_nanos(average_duration(&timers) as u64),
         bench_iterations,
+        timers,
     )
 }
 
@@ -101,13 +324,18 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(outcome: &Outcome<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+    match outcome {
+        Outcome::Solved(result) => {
+            let answer = result.to_string();
+            if answer.contains('\n') {
+                // Answers drawn in block letters are shown both decoded and as drawn.
+                let str = match ocr::decode(&answer) {
+                    Some(text) => format!("{part}: {ANSI_BOLD}{text}{ANSI_RESET} ▼ {duration_str}"),
+                    None => format!("{part}: ▼ {duration_str}"),
+                };
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -125,7 +353,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Outcome::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -133,17 +361,36 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Outcome::Failed(message) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else if message.contains('\n') {
+                print!("\r");
+                println!("{part}: ✖ failed:");
+                println!("{message}");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ {ANSI_ITALIC}{message}{ANSI_RESET}");
+            }
+        }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+/// Submit the answer of a part if it was requested via `--submit`. Parts that failed are never submitted.
+fn submit_outcome<T: Display>(outcome: &Outcome<T>, day: Day, part: u8) {
+    match outcome {
+        Outcome::Solved(answer) => {
+            submit_result(answer, day, part);
+        }
+        Outcome::Failed(_) if submit_part() == Some(part) => {
+            eprintln!("Not submitting part {part}, it failed with an error.");
+        }
+        _ => {}
+    }
+}
+
+/// Parse the part passed to `--submit`, if any.
+fn submit_part() -> Option<u8> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -157,12 +404,23 @@ fn submit_result<T: Display>(
 
     let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
+    let Some(Ok(part_submit)) = args.get(part_index).map(|p| p.parse::<u8>()) else {
         eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
         process::exit(1);
     };
 
-    if part_submit != part {
+    Some(part_submit)
+}
+
+/// Try to submit one part of the solution if:
+///  1. `--submit` was passed for this part.
+///  2. aoc-cli is installed.
+fn submit_result<T: Display>(
+    result: T,
+    day: Day,
+    part: u8,
+) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+    if submit_part()? != part {
         return None;
     }
 
@@ -171,6 +429,75 @@ fn submit_result<T: Display>(
         process::exit(1);
     }
 
+    let mut answer = result.to_string();
+    if answer.contains('\n') {
+        let Some(text) = ocr::decode(&answer) else {
+            eprintln!("Not submitting part {part}, its answer could not be read as letters.");
+            return None;
+        };
+        answer = text;
+    }
+
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(aoc_cli::submit(day, part, &answer))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::{bench, run_timed};
+
+    /// Counts resets and runs, and fails a run that was not preceded by a reset.
+    struct Counter {
+        resets: AtomicUsize,
+        runs: AtomicUsize,
+    }
+
+    impl Counter {
+        const fn new() -> Self {
+            Self {
+                resets: AtomicUsize::new(0),
+                runs: AtomicUsize::new(0),
+            }
+        }
+
+        fn reset(&self) {
+            self.resets.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn run(&self, input: u32) -> u32 {
+            let runs = self.runs.fetch_add(1, Ordering::Relaxed) + 1;
+            assert_eq!(
+                self.resets.load(Ordering::Relaxed),
+                runs,
+                "run without reset"
+            );
+            input
+        }
+    }
+
+    #[test]
+    fn resets_before_first_run() {
+        let counter = Counter::new();
+        let (result, ..) = run_timed(|i| counter.run(i), 7, || counter.reset(), |_| false);
+        assert_eq!(result, 7);
+        assert_eq!(counter.resets.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn resets_before_every_sample() {
+        let counter = Counter::new();
+        let short = Duration::from_millis(5);
+        let (_, samples, timers) = bench(|i| counter.run(i), 7, || counter.reset(), short, short);
+        assert_eq!(timers.len() as u128, samples);
+        assert!(counter.runs.load(Ordering::Relaxed) > timers.len());
+        assert_eq!(
+            counter.resets.load(Ordering::Relaxed),
+            counter.runs.load(Ordering::Relaxed)
+        );
+    }
 }
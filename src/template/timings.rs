@@ -1,10 +1,41 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fmt::Display, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
+use crate::template::fingerprint::Fingerprint;
 use crate::template::Day;
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// Prefix of the stdout line a solution binary prints to report its bench samples.
+pub const SAMPLES_MARKER: &str = "::samples::";
+
+/// Prefix of the stdout line a solution binary prints to report the mean time of its `parse` function.
+pub const PARSE_MARKER: &str = "::parse::";
+
+/// Prefix of the stdout line a solution binary prints to report its [`Fingerprint`] as JSON.
+pub const FINGERPRINT_MARKER: &str = "::fingerprint::";
+
+/// Prefix of the stdout line a solution binary prints to report a part that returned an error.
+pub const FAILURE_MARKER: &str = "::failure::";
+
+/// Environment variable set by `run_multi` to request report lines that are not tied to `--time`.
+pub const REPORT_ENV: &str = "AOC_REPORT";
+
+/// Whether `line` is a machine-readable report line rather than user-facing output.
+pub fn is_report_line(line: &str) -> bool {
+    [
+        SAMPLES_MARKER,
+        PARSE_MARKER,
+        FINGERPRINT_MARKER,
+        FAILURE_MARKER,
+    ]
+    .iter()
+    .any(|marker| line.starts_with(marker))
+}
+
+/// Number of quantiles stored per part, i.e. every 5th percentile from min to max.
+const QUANTILE_COUNT: usize = 21;
+
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
@@ -12,6 +43,290 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// Mean time taken by the day's `parse` function in nanoseconds, if it names one, see
+    /// [`solution!`](crate::solution).
+    pub parse_nanos: Option<f64>,
+    pub samples: Vec<Samples>,
+    /// Machine and build the timing was recorded with.
+    pub fingerprint: Option<Fingerprint>,
+    /// Parts that returned an error, and a failure of part 0 if the binary exited unsuccessfully.
+    /// Not stored, as failed parts have no timing.
+    pub failures: Vec<Failure>,
+}
+
+impl Timing {
+    /// Returns the bench samples recorded for the primary implementation of `part`, if any.
+    pub fn samples_for(&self, part: u8) -> Option<&Samples> {
+        self.samples
+            .iter()
+            .find(|s| s.part == part && s.variant.is_none())
+    }
+
+    /// Returns the bench samples of alternative implementations, see [`Samples::variant`].
+    pub fn variants(&self) -> impl Iterator<Item = &Samples> {
+        self.samples.iter().filter(|s| s.variant.is_some())
+    }
+
+    /// Returns the average duration of `part` in nanoseconds, if it was benched.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        self.samples_for(part).map(|s| s.mean_nanos).or_else(|| {
+            match part {
+                1 => self.part_1.as_deref(),
+                2 => self.part_2.as_deref(),
+                _ => None,
+            }
+            .and_then(parse_nanos)
+        })
+    }
+}
+
+/// How the bench samples of a part were taken.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimingMode {
+    /// Samples ran back to back, so state a run leaves behind may speed up the next one.
+    #[default]
+    Warm,
+    /// The part's `reset` function ran before every sample, see [`solution!`](crate::solution).
+    Reset,
+}
+
+impl Display for TimingMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimingMode::Warm => write!(f, "warm"),
+            TimingMode::Reset => write!(f, "reset"),
+        }
+    }
+}
+
+impl FromStr for TimingMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "warm" => Ok(TimingMode::Warm),
+            "reset" => Ok(TimingMode::Reset),
+            _ => Err(format!("unknown timing mode `{s}`.")),
+        }
+    }
+}
+
+/// Parses a duration as printed by `{:?}`, e.g. `74.13µs`, into nanoseconds.
+pub fn parse_nanos(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let parse_to_float = |postfix: &str| s.split(postfix).next()?.parse::<f64>().ok();
+
+    match s {
+        s if s.contains("ns") => parse_to_float("ns"),
+        s if s.contains("µs") => parse_to_float("µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float("ms").map(|x| x * 1_000_000_f64),
+        _ => parse_to_float("s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+/// Summary of the bench samples of a single part.
+///
+/// Raw samples can number in the millions for fast solutions, so only their
+/// distribution is kept.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Samples {
+    pub part: u8,
+    /// Name of the alternative implementation these samples were taken from.
+    /// [`None`] for the primary implementation of the part.
+    pub variant: Option<String>,
+    pub count: u64,
+    pub mean_nanos: f64,
+    pub stddev_nanos: f64,
+    /// Bytes allocated by a single run of the part, if allocations were tracked.
+    pub alloc_bytes: Option<u64>,
+    pub mode: TimingMode,
+    /// Sample durations in nanoseconds at evenly spaced quantiles, from min to max.
+    pub quantiles: Vec<f64>,
+}
+
+impl Samples {
+    /// Summarises a set of sample durations. Returns [`None`] if there are no samples.
+    pub fn from_durations(part: u8, durations: &[Duration]) -> Option<Self> {
+        if durations.is_empty() {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut nanos: Vec<f64> = durations.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let last = nanos.len() - 1;
+        let quantiles = (0..QUANTILE_COUNT)
+            .map(|i| nanos[(i * last + (QUANTILE_COUNT - 1) / 2) / (QUANTILE_COUNT - 1)])
+            .collect();
+
+        #[allow(clippy::cast_precision_loss)]
+        let count = nanos.len() as f64;
+        let mean_nanos = nanos.iter().sum::<f64>() / count;
+        let variance = nanos.iter().map(|n| (n - mean_nanos).powi(2)).sum::<f64>() / count;
+
+        Some(Self {
+            part,
+            variant: None,
+            count: nanos.len() as u64,
+            mean_nanos,
+            stddev_nanos: variance.sqrt(),
+            alloc_bytes: None,
+            mode: TimingMode::Warm,
+            quantiles,
+        })
+    }
+
+    /// Returns the value at quantile `q` (in `0.0..=1.0`), interpolating between stored quantiles.
+    pub fn quantile(&self, q: f64) -> f64 {
+        let Some(last) = self.quantiles.len().checked_sub(1) else {
+            return self.mean_nanos;
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let pos = q.clamp(0.0, 1.0) * last as f64;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let lower = pos.floor() as usize;
+        let upper = (lower + 1).min(last);
+        #[allow(clippy::cast_precision_loss)]
+        let frac = pos - lower as f64;
+        self.quantiles[lower] + (self.quantiles[upper] - self.quantiles[lower]) * frac
+    }
+
+    pub fn min(&self) -> f64 {
+        self.quantile(0.0)
+    }
+
+    pub fn median(&self) -> f64 {
+        self.quantile(0.5)
+    }
+
+    pub fn max(&self) -> f64 {
+        self.quantile(1.0)
+    }
+}
+
+/// Formats as the line printed by solution binaries, e.g. `::samples:: part=1 count=3 mean=2 stddev=1 quantiles=1,2,3`.
+impl Display for Samples {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let quantiles: Vec<String> = self.quantiles.iter().map(f64::to_string).collect();
+        write!(f, "{SAMPLES_MARKER} part={}", self.part)?;
+        if let Some(variant) = &self.variant {
+            write!(f, " variant={variant}")?;
+        }
+        write!(
+            f,
+            " count={} mean={} stddev={}",
+            self.count, self.mean_nanos, self.stddev_nanos
+        )?;
+        if let Some(alloc_bytes) = self.alloc_bytes {
+            write!(f, " alloc={alloc_bytes}")?;
+        }
+        if self.mode != TimingMode::Warm {
+            write!(f, " mode={}", self.mode)?;
+        }
+        write!(f, " quantiles={}", quantiles.join(","))
+    }
+}
+
+impl FromStr for Samples {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: HashMap<&str, &str> = s
+            .strip_prefix(SAMPLES_MARKER)
+            .ok_or("expected samples marker.")?
+            .split_whitespace()
+            .filter_map(|field| field.split_once('='))
+            .collect();
+
+        let get = |key: &str| fields.get(key).ok_or(format!("expected `{key}` field."));
+
+        Ok(Samples {
+            part: get("part")?.parse().map_err(|_| "invalid `part`.")?,
+            variant: fields.get("variant").map(|v| (*v).to_string()),
+            count: get("count")?.parse().map_err(|_| "invalid `count`.")?,
+            mean_nanos: get("mean")?.parse().map_err(|_| "invalid `mean`.")?,
+            stddev_nanos: get("stddev")?.parse().map_err(|_| "invalid `stddev`.")?,
+            alloc_bytes: fields
+                .get("alloc")
+                .map(|v| v.parse().map_err(|_| "invalid `alloc`."))
+                .transpose()?,
+            mode: fields
+                .get("mode")
+                .map(|v| v.parse())
+                .transpose()?
+                .unwrap_or_default(),
+            quantiles: get("quantiles")?
+                .split(',')
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .map_err(|_| "invalid `quantiles`.")?,
+        })
+    }
+}
+
+/// A part that returned an error instead of an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Failure {
+    /// The failed part, or 0 if the solution binary itself failed, e.g. by panicking.
+    pub part: u8,
+    /// Name of the alternative implementation that failed, see [`Samples::variant`].
+    pub variant: Option<String>,
+    pub message: String,
+}
+
+/// Formats as the line printed by solution binaries, e.g. `::failure:: part=1 message=invalid input`.
+/// Newlines in the message are escaped.
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{FAILURE_MARKER} part={}", self.part)?;
+        if let Some(variant) = &self.variant {
+            write!(f, " variant={variant}")?;
+        }
+        let message = self.message.replace('\\', "\\\\").replace('\n', "\\n");
+        write!(f, " message={message}")
+    }
+}
+
+impl FromStr for Failure {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (fields, message) = s
+            .strip_prefix(FAILURE_MARKER)
+            .ok_or("expected failure marker.")?
+            .split_once(" message=")
+            .ok_or("expected `message` field.")?;
+
+        let fields: HashMap<&str, &str> = fields
+            .split_whitespace()
+            .filter_map(|field| field.split_once('='))
+            .collect();
+
+        let mut unescaped = String::with_capacity(message.len());
+        let mut chars = message.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                unescaped.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => unescaped.push('\n'),
+                Some(c) => unescaped.push(c),
+                None => unescaped.push('\\'),
+            }
+        }
+
+        Ok(Failure {
+            part: fields
+                .get("part")
+                .ok_or("expected `part` field.")?
+                .parse()
+                .map_err(|_| "invalid `part`.")?,
+            variant: fields.get("variant").map(|v| (*v).to_string()),
+            message: unescaped,
+        })
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -51,7 +366,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -60,6 +375,22 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Returns one fingerprint per distinct machine the timings were recorded on.
+    pub fn machines(&self) -> Vec<&Fingerprint> {
+        let mut machines: Vec<&Fingerprint> = vec![];
+        for fingerprint in self.data.iter().filter_map(|t| t.fingerprint.as_ref()) {
+            if !machines.iter().any(|m| m.same_machine(fingerprint)) {
+                machines.push(fingerprint);
+            }
+        }
+        machines
+    }
+
+    /// Whether any part returned an error.
+    pub fn has_failures(&self) -> bool {
+        self.data.iter().any(|t| !t.failures.is_empty())
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
@@ -133,6 +464,24 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "parse_nanos".into(),
+            value.parse_nanos.map_or(JsonValue::Null, JsonValue::Number),
+        );
+
+        map.insert(
+            "samples".into(),
+            JsonValue::Array(value.samples.iter().map(JsonValue::from).collect()),
+        );
+
+        map.insert(
+            "fingerprint".into(),
+            value
+                .fingerprint
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -166,11 +515,116 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: `parse_nanos`, `samples` and `fingerprint` are optional to stay compatible with timings stored by older versions.
+        let parse_nanos = json
+            .get("parse_nanos")
+            .and_then(|v| v.get::<f64>().copied());
+
+        let samples = match json.get("samples") {
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected timing.samples to be an array.")?
+                .iter()
+                .map(Samples::try_from)
+                .collect::<Result<_, _>>()?,
+            None => vec![],
+        };
+
+        let fingerprint = match json.get("fingerprint") {
+            Some(v) if !v.is_null() => Some(Fingerprint::try_from(v)?),
+            _ => None,
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            parse_nanos,
+            samples,
+            fingerprint,
+            failures: vec![],
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Samples> for JsonValue {
+    fn from(value: &Samples) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "variant".into(),
+            value
+                .variant
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("count".into(), JsonValue::Number(value.count as f64));
+        map.insert("mean_nanos".into(), JsonValue::Number(value.mean_nanos));
+        map.insert("stddev_nanos".into(), JsonValue::Number(value.stddev_nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "alloc_bytes".into(),
+            value
+                .alloc_bytes
+                .map_or(JsonValue::Null, |b| JsonValue::Number(b as f64)),
+        );
+        map.insert("mode".into(), JsonValue::String(value.mode.to_string()));
+        map.insert(
+            "quantiles".into(),
+            JsonValue::Array(
+                value
+                    .quantiles
+                    .iter()
+                    .map(|q| JsonValue::Number(*q))
+                    .collect(),
+            ),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Samples {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected samples to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected samples.{key} to be a number."))
+        };
+
+        let quantiles = json
+            .get("quantiles")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .and_then(|v| v.iter().map(|q| q.get::<f64>().copied()).collect())
+            .ok_or("Expected samples.quantiles to be an array of numbers.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Samples {
+            part: number("part")? as u8,
+            variant: json.get("variant").and_then(|v| v.get::<String>()).cloned(),
+            count: number("count")? as u64,
+            mean_nanos: number("mean_nanos")?,
+            stddev_nanos: number("stddev_nanos").unwrap_or(0.0),
+            alloc_bytes: json
+                .get("alloc_bytes")
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|b| b as u64),
+            mode: match json.get("mode").and_then(|v| v.get::<String>()) {
+                Some(mode) => mode.parse()?,
+                None => TimingMode::Warm,
+            },
+            quantiles,
         })
     }
 }
@@ -191,25 +645,131 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    parse_nanos: None,
+                    samples: vec![],
+                    fingerprint: None,
+                    failures: vec![],
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    parse_nanos: None,
+                    samples: vec![],
+                    fingerprint: None,
+                    failures: vec![],
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    parse_nanos: None,
+                    samples: vec![],
+                    fingerprint: None,
+                    failures: vec![],
                 },
             ],
         }
     }
 
+    mod samples {
+        use std::{str::FromStr, time::Duration};
+
+        use crate::template::timings::Samples;
+
+        fn assert_approx_eq(a: f64, b: f64) {
+            assert!(
+                (a - b).abs() < 1.0e-6,
+                "{a} is not approximately equal to {b}"
+            );
+        }
+
+        #[test]
+        fn summarises_durations() {
+            let durations: Vec<Duration> = (1..=101).rev().map(Duration::from_nanos).collect();
+            let samples = Samples::from_durations(1, &durations).unwrap();
+            assert_eq!(samples.count, 101);
+            assert_eq!(samples.mean_nanos, 51.0);
+            assert_approx_eq(samples.stddev_nanos, 29.154_759);
+            assert_eq!(samples.quantiles.len(), 21);
+            assert_eq!(samples.min(), 1.0);
+            assert_eq!(samples.median(), 51.0);
+            assert_eq!(samples.max(), 101.0);
+        }
+
+        #[test]
+        fn handles_no_durations() {
+            assert_eq!(Samples::from_durations(1, &[]), None);
+        }
+
+        #[test]
+        fn round_trips_output_line() {
+            let samples =
+                Samples::from_durations(2, &[Duration::from_nanos(5), Duration::from_nanos(7)])
+                    .unwrap();
+            let line = samples.to_string();
+            assert_eq!(
+                line.starts_with("::samples:: part=2 count=2 mean=6 stddev=1 quantiles="),
+                true
+            );
+            assert_eq!(Samples::from_str(&line).unwrap(), samples);
+        }
+
+        #[test]
+        fn round_trips_alloc_bytes() {
+            let mut samples = Samples::from_durations(1, &[Duration::from_nanos(5)]).unwrap();
+            samples.alloc_bytes = Some(1024);
+            let line = samples.to_string();
+            assert_eq!(line.contains(" alloc=1024 "), true);
+            assert_eq!(Samples::from_str(&line).unwrap(), samples);
+        }
+
+        #[test]
+        fn round_trips_mode() {
+            use crate::template::timings::TimingMode;
+
+            let mut samples = Samples::from_durations(1, &[Duration::from_nanos(5)]).unwrap();
+            assert_eq!(samples.to_string().contains("mode="), false);
+            samples.mode = TimingMode::Reset;
+            let line = samples.to_string();
+            assert_eq!(line.contains(" mode=reset "), true);
+            assert_eq!(Samples::from_str(&line).unwrap(), samples);
+        }
+
+        #[test]
+        fn round_trips_failure() {
+            use crate::template::timings::Failure;
+
+            let failure = Failure {
+                part: 1,
+                variant: None,
+                message: "line 2:\n  a\\b\n  ^ expected digit".into(),
+            };
+            let line = failure.to_string();
+            assert_eq!(line.contains('\n'), false);
+            assert_eq!(Failure::from_str(&line).unwrap(), failure);
+        }
+
+        #[test]
+        fn round_trips_variant() {
+            let mut samples = Samples::from_durations(2, &[Duration::from_nanos(5)]).unwrap();
+            samples.variant = Some("naive".into());
+            let line = samples.to_string();
+            assert_eq!(
+                line.starts_with("::samples:: part=2 variant=naive count=1"),
+                true
+            );
+            assert_eq!(Samples::from_str(&line).unwrap(), samples);
+        }
+    }
+
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{TimingMode, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -221,6 +781,38 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.samples.len(), 0);
+        }
+
+        #[test]
+        fn handles_json_mode() {
+            let json = r#"{ "data": [{ "day": "11", "part_1": "1ms", "part_2": "1ms", "total_nanos": 2000000, "samples": [{ "part": 1, "count": 3, "mean_nanos": 1000000, "quantiles": [1, 2, 3] }, { "part": 2, "count": 3, "mean_nanos": 1000000, "mode": "reset", "quantiles": [1, 2, 3] }] }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(
+                timings.data[0].samples_for(1).unwrap().mode,
+                TimingMode::Warm
+            );
+            assert_eq!(
+                timings.data[0].samples_for(2).unwrap().mode,
+                TimingMode::Reset
+            );
+        }
+
+        #[test]
+        fn handles_json_parse_time() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "parse_nanos": 1500 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].parse_nanos, Some(1500.0));
+        }
+
+        #[test]
+        fn handles_json_samples() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "samples": [{ "part": 1, "count": 3, "mean_nanos": 1000000, "quantiles": [1, 2, 3] }] }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let samples = timings.data[0].samples_for(1).unwrap();
+            assert_eq!(samples.count, 3);
+            assert_eq!(samples.quantiles, vec![1.0, 2.0, 3.0]);
+            assert_eq!(timings.data[0].samples_for(2), None);
         }
 
         #[test]
@@ -282,10 +874,14 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    parse_nanos: None,
+                    samples: vec![],
+                    fingerprint: None,
+                    failures: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -296,10 +892,14 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    parse_nanos: None,
+                    samples: vec![],
+                    fingerprint: None,
+                    failures: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -310,10 +910,14 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    parse_nanos: None,
+                    samples: vec![],
+                    fingerprint: None,
+                    failures: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -334,6 +938,10 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    parse_nanos: None,
+                    samples: vec![],
+                    fingerprint: None,
+                    failures: vec![],
                 }],
             };
             let merged = timings.merge(&other);
@@ -354,6 +962,10 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    parse_nanos: None,
+                    samples: vec![],
+                    fingerprint: None,
+                    failures: vec![],
                 }],
             };
             let merged = timings.merge(&other);
@@ -0,0 +1,185 @@
+/// Debug output for solutions that can stay in the code.
+///
+/// [`trace!`](crate::trace!) prints a tagged line to stderr, prefixed with the part that is
+/// running, and [`trace_scope!`](crate::trace_scope!) does the same but indents everything traced
+/// until the end of the enclosing block:
+///
+/// ```ignore
+/// trace_scope!("search", "from {start:?}");
+/// for next in neighbours {
+///     trace!("search", "visiting {next:?}");
+/// }
+/// ```
+///
+/// Tracing is only compiled in with the `trace` feature, which `cargo solve <day> --verbose`
+/// enables, and never in release builds, so traces can stay in while benching. Otherwise the
+/// arguments are still type-checked but never evaluated. Set `AOC_TRACE` to a comma-separated
+/// list of tags to only print those. Nothing is traced while a part is benched.
+use std::cell::Cell;
+use std::env;
+use std::fmt::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use crate::template::{ANSI_ITALIC, ANSI_RESET};
+
+/// Prints a tagged line with the arguments of [`format!`], see the [module docs](crate::trace).
+#[macro_export]
+macro_rules! trace {
+    ($tag:expr, $($arg:tt)+) => {
+        if cfg!(all(feature = "trace", debug_assertions)) {
+            $crate::trace::emit($tag, format_args!($($arg)+));
+        }
+    };
+}
+
+/// Prints a tagged line like [`trace!`](crate::trace!) and indents further traces until the end
+/// of the enclosing block.
+#[macro_export]
+macro_rules! trace_scope {
+    ($tag:expr, $($arg:tt)+) => {
+        let _scope = if cfg!(all(feature = "trace", debug_assertions)) {
+            $crate::trace::enter($tag, format_args!($($arg)+))
+        } else {
+            $crate::trace::Scope::default()
+        };
+    };
+}
+
+static TRACING: AtomicBool = AtomicBool::new(true);
+
+/// The part that is running, e.g. `part 1` or `part 2/fast`.
+static PART: Mutex<String> = Mutex::new(String::new());
+
+thread_local! {
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Prefixes traces with `part` and `variant` until the returned guard is dropped.
+pub(crate) fn part(part: u8, variant: Option<&str>) -> PartGuard {
+    let label = match variant {
+        Some(variant) => format!("part {part}/{variant}"),
+        None => format!("part {part}"),
+    };
+    if let Ok(mut current) = PART.lock() {
+        *current = label;
+    }
+    PartGuard
+}
+
+pub(crate) struct PartGuard;
+
+impl Drop for PartGuard {
+    fn drop(&mut self) {
+        if let Ok(mut current) = PART.lock() {
+            current.clear();
+        }
+    }
+}
+
+/// Stops tracing until the returned guard is dropped, e.g. while the runner benches a part.
+pub(crate) fn pause() -> Pause {
+    Pause(TRACING.swap(false, Ordering::Relaxed))
+}
+
+pub(crate) struct Pause(bool);
+
+impl Drop for Pause {
+    fn drop(&mut self) {
+        TRACING.store(self.0, Ordering::Relaxed);
+    }
+}
+
+#[doc(hidden)]
+pub fn emit(tag: &str, args: fmt::Arguments) {
+    if !TRACING.load(Ordering::Relaxed) || !filter().allows(tag) {
+        return;
+    }
+    let part = PART.lock().map(|part| part.clone()).unwrap_or_default();
+    eprintln!("{}", line(&part, DEPTH.with(Cell::get), tag, args));
+}
+
+#[doc(hidden)]
+pub fn enter(tag: &str, args: fmt::Arguments) -> Scope {
+    emit(tag, args);
+    DEPTH.with(|depth| depth.set(depth.get() + 1));
+    Scope { active: true }
+}
+
+/// Indents traces while alive, created by [`trace_scope!`](crate::trace_scope!).
+#[doc(hidden)]
+#[derive(Default)]
+pub struct Scope {
+    active: bool,
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        if self.active {
+            DEPTH.with(|depth| depth.set(depth.get().saturating_sub(1)));
+        }
+    }
+}
+
+fn line(part: &str, depth: usize, tag: &str, args: fmt::Arguments) -> String {
+    let mut line = String::new();
+    if !part.is_empty() {
+        let _ = write!(line, "{ANSI_ITALIC}{part}{ANSI_RESET} ");
+    }
+    let _ = write!(line, "{:indent$}{tag}: {args}", "", indent = depth * 2);
+    line
+}
+
+/// The tags to print, from `AOC_TRACE`.
+struct Filter(Option<Vec<String>>);
+
+impl Filter {
+    fn parse(tags: &str) -> Self {
+        let tags: Vec<String> = tags
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(String::from)
+            .collect();
+        Self((!tags.is_empty()).then_some(tags))
+    }
+
+    fn allows(&self, tag: &str) -> bool {
+        self.0
+            .as_ref()
+            .is_none_or(|tags| tags.iter().any(|t| t == tag))
+    }
+}
+
+fn filter() -> &'static Filter {
+    static FILTER: OnceLock<Filter> = OnceLock::new();
+    FILTER.get_or_init(|| Filter::parse(&env::var("AOC_TRACE").unwrap_or_default()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{line, Filter};
+    use crate::template::{ANSI_ITALIC, ANSI_RESET};
+
+    #[test]
+    fn filters_tags() {
+        let filter = Filter::parse("search, blink,");
+        assert!(filter.allows("search"));
+        assert!(filter.allows("blink"));
+        assert!(!filter.allows("parse"));
+
+        assert!(Filter::parse("").allows("parse"));
+        assert!(Filter::parse(" , ").allows("parse"));
+    }
+
+    #[test]
+    fn indents_lines() {
+        assert_eq!(
+            line("part 1", 2, "search", format_args!("at {}", 3)),
+            format!("{ANSI_ITALIC}part 1{ANSI_RESET}     search: at 3")
+        );
+        assert_eq!(line("", 0, "parse", format_args!("ok")), "parse: ok");
+    }
+}
@@ -0,0 +1,359 @@
+/// Image and animation encoders for recorded frames. Images draw every cell as a square of
+/// `scale` pixels in the cell's colour.
+use std::fmt::Write;
+
+use rustc_hash::FxHashMap;
+
+use super::{Frame, Rgb, VizError};
+
+/// The pixels of `frame`, row by row, and the image width and height.
+fn rasterise(frame: &Frame, scale: usize) -> (usize, usize, Vec<Rgb>) {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    let mut pixels = Vec::with_capacity(width * height);
+    for row in frame.rows() {
+        for _ in 0..scale {
+            for cell in row {
+                pixels.extend(std::iter::repeat_n(cell.colour, scale));
+            }
+        }
+    }
+    (width, height, pixels)
+}
+
+/// A binary PPM (`P6`) image.
+pub fn ppm(frame: &Frame, scale: usize) -> Vec<u8> {
+    let (width, height, pixels) = rasterise(frame, scale);
+    let mut out = format!("P6\n{width} {height}\n255\n").into_bytes();
+    out.extend(pixels.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// A truecolour PNG image.
+#[allow(clippy::cast_possible_truncation)]
+pub fn png(frame: &Frame, scale: usize) -> Vec<u8> {
+    let (width, height, pixels) = rasterise(frame, scale);
+
+    let mut header = Vec::with_capacity(13);
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // 8 bits per channel, RGB, default compression, filtering and no interlacing.
+    header.extend([8, 2, 0, 0, 0]);
+
+    let mut raw = Vec::with_capacity(height * (width * 3 + 1));
+    for row in pixels.chunks(width.max(1)) {
+        // every row starts with its filter type, none.
+        raw.push(0);
+        raw.extend(row.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+    }
+
+    let mut out = PNG_SIGNATURE.to_vec();
+    png_chunk(&mut out, b"IHDR", &header);
+    png_chunk(
+        &mut out,
+        b"IDAT",
+        &miniz_oxide::deflate::compress_to_vec_zlib(&raw, 6),
+    );
+    png_chunk(&mut out, b"IEND", &[]);
+    out
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |crc, &byte| {
+        (0..8).fold(crc ^ u32::from(byte), |crc, _| {
+            (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg())
+        })
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An animated, looping GIF. All frames share one palette of at most 256 colours.
+#[allow(clippy::cast_possible_truncation)]
+pub fn gif(frames: &[Frame], scale: usize, delay: u16) -> Result<Vec<u8>, VizError> {
+    let mut palette: Vec<Rgb> = vec![];
+    let mut indices: FxHashMap<Rgb, u8> = FxHashMap::default();
+    for cell in frames.iter().flat_map(Frame::rows).flatten() {
+        indices.entry(cell.colour).or_insert_with(|| {
+            palette.push(cell.colour);
+            (palette.len() - 1) as u8
+        });
+    }
+    if palette.len() > 256 {
+        return Err(VizError::TooManyColours(palette.len()));
+    }
+
+    // the palette size is a power of two of at least 4 colours.
+    let bits = palette.len().max(4).next_power_of_two().trailing_zeros() as u8;
+    palette.resize(1 << bits, Rgb::BLACK);
+
+    let (width, height) = frames
+        .first()
+        .map_or((0, 0), |f| (f.width() * scale, f.height() * scale));
+
+    let mut out = b"GIF89a".to_vec();
+    out.extend((width as u16).to_le_bytes());
+    out.extend((height as u16).to_le_bytes());
+    out.extend([0x80 | ((bits - 1) << 4) | (bits - 1), 0, 0]);
+    out.extend(palette.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+
+    // loop forever.
+    out.extend([0x21, 0xff, 0x0b]);
+    out.extend(b"NETSCAPE2.0");
+    out.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+
+    for frame in frames {
+        let (width, height, pixels) = rasterise(frame, scale);
+
+        out.extend([0x21, 0xf9, 0x04, 0x04]);
+        out.extend(delay.to_le_bytes());
+        out.extend([0x00, 0x00]);
+
+        out.push(0x2c);
+        out.extend([0, 0, 0, 0]);
+        out.extend((width as u16).to_le_bytes());
+        out.extend((height as u16).to_le_bytes());
+        out.push(0);
+
+        let pixels: Vec<u8> = pixels.iter().map(|colour| indices[colour]).collect();
+        out.push(bits);
+        for block in lzw(&pixels, bits).chunks(255) {
+            out.push(block.len() as u8);
+            out.extend(block);
+        }
+        out.push(0);
+    }
+
+    out.push(0x3b);
+    Ok(out)
+}
+
+/// Compresses palette indices with the variable-width LZW of GIF.
+fn lzw(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    const MAX_CODE: u16 = 4096;
+
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut code_size = min_code_size + 1;
+    let mut next = end + 1;
+    let mut codes: FxHashMap<(u16, u8), u16> = FxHashMap::default();
+    let mut writer = BitWriter::default();
+
+    writer.write(clear, code_size);
+
+    let mut prefix: Option<u16> = None;
+    for &index in indices {
+        let Some(current) = prefix else {
+            prefix = Some(u16::from(index));
+            continue;
+        };
+        if let Some(&code) = codes.get(&(current, index)) {
+            prefix = Some(code);
+            continue;
+        }
+
+        writer.write(current, code_size);
+        if next == MAX_CODE {
+            writer.write(clear, code_size);
+            codes.clear();
+            code_size = min_code_size + 1;
+            next = end + 1;
+        } else {
+            codes.insert((current, index), next);
+            // the decoder adds codes one step later, so it widens codes once this one is used.
+            if next == 1 << code_size {
+                code_size += 1;
+            }
+            next += 1;
+        }
+        prefix = Some(u16::from(index));
+    }
+
+    if let Some(current) = prefix {
+        writer.write(current, code_size);
+    }
+    writer.write(end, code_size);
+    writer.finish()
+}
+
+/// Packs codes least significant bit first.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    len: u8,
+}
+
+impl BitWriter {
+    #[allow(clippy::cast_possible_truncation)]
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= u32::from(code) << self.len;
+        self.len += size;
+        while self.len >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.len -= 8;
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Marks the start of a frame in ANSI files: moves the cursor to the top left.
+pub const ANSI_FRAME: &str = "\x1b[H";
+
+/// All frames as coloured text. Every frame starts with [`ANSI_FRAME`], so that printing the
+/// file draws the frames over each other.
+pub fn ansi(frames: &[Frame]) -> String {
+    let mut out = String::from("\x1b[2J");
+    for frame in frames {
+        out.push_str(ANSI_FRAME);
+        ansi_frame(&mut out, frame);
+    }
+    out
+}
+
+/// Appends the rows of `frame` with colour codes, resetting the colour at the end of each row.
+pub fn ansi_frame(out: &mut String, frame: &Frame) {
+    for row in frame.rows() {
+        let mut colour = None;
+        for cell in row {
+            if colour != Some(cell.colour) {
+                let Rgb(r, g, b) = cell.colour;
+                let _ = write!(out, "\x1b[38;2;{r};{g};{b}m");
+                colour = Some(cell.colour);
+            }
+            out.push(cell.glyph);
+        }
+        out.push_str("\x1b[0m\n");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ansi, crc32, gif, lzw, png, ppm, ANSI_FRAME};
+    use crate::viz::{Cell, Frame, Rgb};
+
+    fn frame() -> Frame {
+        "#.\n^#"
+            .parse::<crate::grid::Grid<u8>>()
+            .unwrap()
+            .map(|&c| Cell::from(c))
+    }
+
+    /// Decodes GIF LZW data into palette indices, to check the encoder against.
+    fn unlzw(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let mut table: Vec<Vec<u8>> = vec![];
+        let mut code_size = min_code_size + 1;
+        let mut previous: Option<usize> = None;
+        let mut out = vec![];
+        let (mut buffer, mut len, mut bytes) = (0u32, 0u8, bytes.iter());
+
+        loop {
+            while len < code_size {
+                buffer |= u32::from(*bytes.next().unwrap()) << len;
+                len += 8;
+            }
+            let code = (buffer & ((1 << code_size) - 1)) as usize;
+            buffer >>= code_size;
+            len -= code_size;
+
+            if code == clear {
+                table = (0..clear).map(|i| vec![i as u8]).collect();
+                table.extend([vec![], vec![]]);
+                code_size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return out;
+            }
+
+            let entry = match (code < table.len(), previous) {
+                (true, _) => table[code].clone(),
+                (false, Some(p)) => [table[p].clone(), vec![table[p][0]]].concat(),
+                (false, None) => panic!("invalid code"),
+            };
+            if let Some(p) = previous {
+                if table.len() < 4096 {
+                    table.push([table[p].clone(), vec![entry[0]]].concat());
+                }
+            }
+            if table.len() == 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+            out.extend(&entry);
+            previous = Some(code);
+        }
+    }
+
+    #[test]
+    fn round_trips_lzw() {
+        let repetitive: Vec<u8> = (0..20_000).map(|i| (i / 7 % 3) as u8).collect();
+        let noisy: Vec<u8> = (0..20_000u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+            .collect();
+        for (data, bits) in [(repetitive, 2), (noisy, 8), (vec![1], 2), (vec![], 2)] {
+            assert_eq!(unlzw(&lzw(&data, bits), bits), data);
+        }
+    }
+
+    #[test]
+    fn encodes_images() {
+        let ppm = ppm(&frame(), 2);
+        assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(ppm.len(), 11 + 4 * 4 * 3);
+        assert_eq!(ppm[11..14], [Rgb::GREY.0, Rgb::GREY.1, Rgb::GREY.2]);
+
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        let png = png(&frame(), 3);
+        assert!(png.starts_with(&[0x89, b'P', b'N', b'G']));
+        assert_eq!(png[16..24], [0, 0, 0, 6, 0, 0, 0, 6]);
+        assert!(png.ends_with(&[b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+    }
+
+    #[test]
+    fn encodes_animations() {
+        let animation = gif(&[frame(), frame()], 1, 5).unwrap();
+        assert!(animation.starts_with(b"GIF89a\x02\x00\x02\x00"));
+        assert_eq!(animation.iter().filter(|&&b| b == 0x2c).count(), 2);
+        assert_eq!(animation.last(), Some(&0x3b));
+
+        let many = Frame::from_vec(
+            300,
+            (0..300)
+                .map(|i| Cell::new('x', Rgb(i as u8, (i / 256) as u8, 0)))
+                .collect(),
+        );
+        assert!(gif(&[many], 1, 5).is_err());
+
+        let ansi = ansi(&[frame(), frame()]);
+        assert_eq!(ansi.matches(ANSI_FRAME).count(), 2);
+        assert!(ansi.contains("\x1b[38;2;138;143;152m#"));
+    }
+}
@@ -0,0 +1,152 @@
+/// Frames of character or colour grids, recorded by solutions to see how a simulation evolves.
+///
+/// A [`Recorder`] collects [`Frame`]s while a part runs and writes them to `target/viz/<day>/`
+/// when finished, as images, an animated GIF or an ANSI file that replays with `cat`:
+///
+/// ```ignore
+/// let mut recorder = Recorder::new(DAY, "guard");
+/// for step in steps {
+///     recorder.frame(|| map.map(|&c| Cell::from(c)));
+/// }
+/// recorder.finish();
+/// ```
+///
+/// Without the `viz` feature, recorders are empty and frames are never built, so recording
+/// costs nothing in benchmark builds. Frames are only recorded when a solution binary runs a
+/// part, not while it is benched nor in tests.
+///
+/// With `cargo solve <day> --visualize`, finished recordings are also played in the terminal
+/// after the part prints its answer.
+use std::error::Error;
+use std::fmt::Display;
+use std::io;
+
+use crate::grid::Grid;
+
+#[cfg(not(feature = "viz"))]
+mod disabled;
+#[cfg(feature = "viz")]
+mod encode;
+#[cfg(feature = "viz")]
+mod play;
+#[cfg(feature = "viz")]
+mod recorder;
+
+#[cfg(not(feature = "viz"))]
+pub use disabled::Recorder;
+#[cfg(feature = "viz")]
+pub(crate) use play::play_queued;
+#[cfg(feature = "viz")]
+pub use recorder::Recorder;
+#[cfg(feature = "viz")]
+pub(crate) use recorder::{pause, record};
+
+/// A single frame, one [`Cell`] per grid position.
+pub type Frame = Grid<Cell>;
+
+/// A colour in 24-bit RGB.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0x00, 0x00, 0x00);
+    pub const WHITE: Rgb = Rgb(0xff, 0xff, 0xff);
+    pub const GREY: Rgb = Rgb(0x8a, 0x8f, 0x98);
+    pub const DARK: Rgb = Rgb(0x2b, 0x2f, 0x36);
+    pub const RED: Rgb = Rgb(0xe0, 0x4f, 0x4f);
+    pub const GREEN: Rgb = Rgb(0x5f, 0xc8, 0x5f);
+    pub const BLUE: Rgb = Rgb(0x4f, 0x8f, 0xe0);
+    pub const YELLOW: Rgb = Rgb(0xf0, 0xc6, 0x4a);
+}
+
+/// Colours of characters that have no obvious colour of their own, picked by character code.
+const PALETTE: [Rgb; 6] = [
+    Rgb::RED,
+    Rgb::GREEN,
+    Rgb::BLUE,
+    Rgb::YELLOW,
+    Rgb(0xc0, 0x6f, 0xe0),
+    Rgb(0x4f, 0xd0, 0xd0),
+];
+
+/// A character drawn in a colour. Images only show the colour, ANSI files both.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub glyph: char,
+    pub colour: Rgb,
+}
+
+impl Cell {
+    pub fn new(glyph: char, colour: Rgb) -> Self {
+        Self { glyph, colour }
+    }
+}
+
+/// Picks a colour for the character: walls `#` are grey, floors `.` dark and anything else a
+/// colour of its own, so that e.g. antennas of the same frequency match.
+impl From<char> for Cell {
+    fn from(glyph: char) -> Self {
+        let colour = match glyph {
+            '#' => Rgb::GREY,
+            '.' => Rgb::DARK,
+            ' ' => Rgb::BLACK,
+            c => PALETTE[c as usize % PALETTE.len()],
+        };
+        Self::new(glyph, colour)
+    }
+}
+
+impl From<u8> for Cell {
+    fn from(glyph: u8) -> Self {
+        Self::from(char::from(glyph))
+    }
+}
+
+impl From<Cell> for char {
+    fn from(cell: Cell) -> Self {
+        cell.glyph
+    }
+}
+
+/// The files a [`Recorder`] writes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// One PPM image per frame, `<name>-0001.ppm` etc.
+    Ppm,
+    /// One PNG image per frame, `<name>-0001.png` etc.
+    Png,
+    /// An animated GIF of all frames, `<name>.gif`. Frames may use at most 256 colours.
+    Gif,
+    /// All frames with terminal colour codes, `<name>.ansi`, which replays with `cat`.
+    Ansi,
+}
+
+/// An error which can be returned when writing recorded frames.
+#[derive(Debug)]
+pub enum VizError {
+    ProjectRootNotFound,
+    TooManyColours(usize),
+    IO(io::Error),
+}
+
+impl From<io::Error> for VizError {
+    fn from(e: io::Error) -> Self {
+        VizError::IO(e)
+    }
+}
+
+impl Error for VizError {}
+
+impl Display for VizError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VizError::ProjectRootNotFound => {
+                write!(f, "could not locate the project root to write frames to.")
+            }
+            VizError::TooManyColours(count) => {
+                write!(f, "frames use {count} colours, a GIF can only show 256.")
+            }
+            VizError::IO(e) => write!(f, "could not write frames: {e}"),
+        }
+    }
+}
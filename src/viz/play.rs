@@ -0,0 +1,219 @@
+/// Playback of recorded frames in the terminal for `cargo solve <day> --visualize`. Recorders
+/// queue their frames here when they finish, and the runner plays them once a part has printed
+/// its answer, so that playback is never timed.
+use std::env;
+use std::fs::File;
+use std::io::{self, IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use super::{encode, Frame};
+
+const DEFAULT_FPS: u32 = 10;
+
+const ENTER_SCREEN: &str = "\x1b[?1049h\x1b[?25l\x1b[2J";
+const LEAVE_SCREEN: &str = "\x1b[?25h\x1b[?1049l";
+
+/// Recordings waiting to be played, with their names.
+static QUEUE: Mutex<Vec<(String, Vec<Frame>)>> = Mutex::new(Vec::new());
+
+/// Whether the solution was started with `--visualize`.
+pub(crate) fn is_enabled() -> bool {
+    env::args().any(|x| x == "--visualize")
+}
+
+pub(crate) fn queue(name: String, frames: Vec<Frame>) {
+    if let Ok(mut queue) = QUEUE.lock() {
+        queue.push((name, frames));
+    }
+}
+
+/// Plays the recordings queued since the last call, one after the other.
+pub(crate) fn play_queued() {
+    let recordings = match QUEUE.lock() {
+        Ok(mut queue) => std::mem::take(&mut *queue),
+        Err(_) => return,
+    };
+    for (name, frames) in recordings {
+        if let Err(e) = play(&name, &frames, fps()) {
+            eprintln!("viz: could not play `{name}`: {e}");
+        }
+    }
+}
+
+/// The frame rate passed with `--fps <n>`.
+fn fps() -> u32 {
+    let args: Vec<String> = env::args().collect();
+    args.iter()
+        .position(|x| x == "--fps")
+        .and_then(|i| args.get(i + 1)?.parse().ok())
+        .unwrap_or(DEFAULT_FPS)
+        .clamp(1, 1000)
+}
+
+/// Plays `frames` on the alternate screen until the user quits. If there is no terminal to
+/// play in, e.g. when the output is piped, only the last frame is printed.
+fn play(name: &str, frames: &[Frame], fps: u32) -> io::Result<()> {
+    let Some(last) = frames.last() else {
+        return Ok(());
+    };
+
+    let Some(keys) = io::stdout().is_terminal().then(Keys::listen).flatten() else {
+        let mut out = format!("{name}, frame {} of {}:\n", frames.len(), frames.len());
+        for row in last.rows() {
+            out.extend(row.iter().map(|cell| cell.glyph));
+            out.push('\n');
+        }
+        return io::stdout().write_all(out.as_bytes());
+    };
+
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(ENTER_SCREEN.as_bytes())?;
+    let result = run(&mut stdout, &keys, name, frames, fps);
+    stdout.write_all(LEAVE_SCREEN.as_bytes())?;
+    stdout.flush()?;
+    result
+}
+
+fn run(
+    out: &mut impl Write,
+    keys: &Keys,
+    name: &str,
+    frames: &[Frame],
+    fps: u32,
+) -> io::Result<()> {
+    let delay = Duration::from_secs(1) / fps;
+    let mut index = 0;
+    let mut paused = false;
+
+    loop {
+        draw(out, name, frames, index, paused, fps)?;
+
+        let key = if paused {
+            keys.receiver
+                .recv()
+                .map_err(|_| RecvTimeoutError::Disconnected)
+        } else {
+            keys.receiver.recv_timeout(delay)
+        };
+
+        match key {
+            // not escape, which also starts the sequences that arrow keys send.
+            Ok(b'q' | 0x03) | Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            Ok(b' ') => paused = !paused,
+            Ok(b'n') => {
+                paused = true;
+                index = (index + 1).min(frames.len() - 1);
+            }
+            Ok(b'p') => {
+                paused = true;
+                index = index.saturating_sub(1);
+            }
+            Ok(_) => {}
+            // the last frame stays on screen until the user quits.
+            Err(RecvTimeoutError::Timeout) if index + 1 == frames.len() => paused = true,
+            Err(RecvTimeoutError::Timeout) => index += 1,
+        }
+    }
+}
+
+fn draw(
+    out: &mut impl Write,
+    name: &str,
+    frames: &[Frame],
+    index: usize,
+    paused: bool,
+    fps: u32,
+) -> io::Result<()> {
+    let mut screen = String::from(encode::ANSI_FRAME);
+    encode::ansi_frame(&mut screen, &frames[index]);
+    let state = if paused { "paused" } else { "playing" };
+    screen.push_str(&format!(
+        "\n{name}: frame {}/{} at {fps} fps, {state}    \
+        [space] pause  [n] next  [p] previous  [q] quit\x1b[K",
+        index + 1,
+        frames.len(),
+    ));
+    out.write_all(screen.as_bytes())?;
+    out.flush()
+}
+
+/// Key presses read from the terminal, which is switched to unbuffered input without echo until
+/// this is dropped.
+struct Keys {
+    receiver: Receiver<u8>,
+    stop: Arc<AtomicBool>,
+    reader: Option<JoinHandle<()>>,
+    saved: String,
+}
+
+impl Keys {
+    /// Starts reading keys, or returns `None` if there is no terminal to read them from.
+    fn listen() -> Option<Self> {
+        let saved = stty(&["-g"])?;
+        // reads return after at most 100ms, so that the reader can notice when to stop.
+        stty(&["-icanon", "-echo", "-isig", "min", "0", "time", "1"])?;
+
+        let Ok(mut tty) = File::open("/dev/tty") else {
+            stty(&[saved.trim()]);
+            return None;
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let reader = thread::spawn({
+            let stop = Arc::clone(&stop);
+            move || {
+                let mut buf = [0; 16];
+                while !stop.load(Ordering::Relaxed) {
+                    match tty.read(&mut buf) {
+                        Ok(n) => {
+                            for &key in &buf[..n] {
+                                if sender.send(key).is_err() {
+                                    return;
+                                }
+                            }
+                        }
+                        Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                        Err(_) => return,
+                    }
+                }
+            }
+        });
+
+        Some(Self {
+            receiver,
+            stop,
+            reader: Some(reader),
+            saved,
+        })
+    }
+}
+
+impl Drop for Keys {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+        stty(&[self.saved.trim()]);
+    }
+}
+
+/// Runs `stty` on the controlling terminal and returns its output if it succeeded.
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(File::open("/dev/tty").ok()?)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,167 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::{encode, play, Format, Frame, VizError};
+use crate::template::{project_root, Day};
+
+/// Off until the runner starts recording, so that tests calling parts directly write no files.
+static RECORDING: AtomicBool = AtomicBool::new(false);
+
+/// Lets recorders created from now on record frames, which the runner does before running a part.
+pub(crate) fn record() {
+    RECORDING.store(true, Ordering::Relaxed);
+}
+
+/// Stops recorders created until the returned guard is dropped from recording frames, e.g. while
+/// the runner benches a part.
+pub(crate) fn pause() -> Pause {
+    Pause(RECORDING.swap(false, Ordering::Relaxed))
+}
+
+pub(crate) struct Pause(bool);
+
+impl Drop for Pause {
+    fn drop(&mut self) {
+        RECORDING.store(self.0, Ordering::Relaxed);
+    }
+}
+
+/// Collects frames and writes them when finished, see the [module docs](super).
+#[derive(Debug)]
+pub struct Recorder {
+    day: Day,
+    name: String,
+    formats: Vec<Format>,
+    scale: usize,
+    delay: u16,
+    frames: Vec<Frame>,
+    active: bool,
+}
+
+impl Recorder {
+    /// A recorder writing `<name>.gif` and `<name>.ansi` to `target/viz/<day>/`. Images are
+    /// scaled to 4 pixels per cell and animations show 20 frames per second.
+    pub fn new(day: Day, name: &str) -> Self {
+        Self {
+            day,
+            name: name.to_string(),
+            formats: vec![Format::Gif, Format::Ansi],
+            scale: 4,
+            delay: 5,
+            frames: vec![],
+            active: RECORDING.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Sets the files to write.
+    #[must_use]
+    pub fn formats(mut self, formats: &[Format]) -> Self {
+        self.formats = formats.to_vec();
+        self
+    }
+
+    /// Sets the width and height of a cell in images, in pixels.
+    #[must_use]
+    pub fn scale(mut self, pixels: usize) -> Self {
+        self.scale = pixels.max(1);
+        self
+    }
+
+    /// Sets how long a frame of the GIF is shown.
+    #[must_use]
+    pub fn delay(mut self, centiseconds: u16) -> Self {
+        self.delay = centiseconds;
+        self
+    }
+
+    /// Records the frame built by `frame`, which is only called while recording.
+    pub fn frame(&mut self, frame: impl FnOnce() -> Frame) {
+        if self.active {
+            self.frames.push(frame());
+        }
+    }
+
+    /// Writes the recorded frames and reports where to on stderr. With `--visualize`, the frames
+    /// are then played once the part has printed its answer.
+    pub fn finish(self) {
+        if !self.active || self.frames.is_empty() {
+            return;
+        }
+        match self.write() {
+            Ok(dir) => eprintln!(
+                "viz: {} frames of `{}` written to {}",
+                self.frames.len(),
+                self.name,
+                dir.display()
+            ),
+            Err(e) => eprintln!("viz: {e}"),
+        }
+        if play::is_enabled() {
+            play::queue(self.name, self.frames);
+        }
+    }
+
+    fn write(&self) -> Result<PathBuf, VizError> {
+        let dir = project_root()
+            .ok_or(VizError::ProjectRootNotFound)?
+            .join("target")
+            .join("viz")
+            .join(self.day.to_string());
+        fs::create_dir_all(&dir)?;
+
+        for &format in &self.formats {
+            let path =
+                |suffix: &str| dir.join(format!("{}{suffix}.{}", self.name, format.extension()));
+            match format {
+                Format::Ppm | Format::Png => {
+                    self.remove_images(&dir, format)?;
+                    for (i, frame) in self.frames.iter().enumerate() {
+                        let image = if format == Format::Ppm {
+                            encode::ppm(frame, self.scale)
+                        } else {
+                            encode::png(frame, self.scale)
+                        };
+                        fs::write(path(&format!("-{:04}", i + 1)), image)?;
+                    }
+                }
+                Format::Gif => {
+                    fs::write(path(""), encode::gif(&self.frames, self.scale, self.delay)?)?;
+                }
+                Format::Ansi => fs::write(path(""), encode::ansi(&self.frames))?,
+            }
+        }
+
+        Ok(dir)
+    }
+
+    /// Removes images of an earlier recording, which may have had more frames.
+    fn remove_images(&self, dir: &Path, format: Format) -> Result<(), VizError> {
+        let prefix = format!("{}-", self.name);
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let is_image = path
+                .file_stem()
+                .and_then(|stem| stem.to_str()?.strip_prefix(&prefix))
+                .is_some_and(|number| number.bytes().all(|b| b.is_ascii_digit()))
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext == format.extension());
+            if is_image {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+            Format::Gif => "gif",
+            Format::Ansi => "ansi",
+        }
+    }
+}